@@ -0,0 +1,95 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use violet::data::pathtree::PathTree;
+
+const WORDS: [&str; 12] = [
+    "add", "remove", "list", "show", "what", "is", "the", "please", "say", "alias", "time",
+    "command",
+];
+const TREE_SIZES: [usize; 3] = [100, 1_000, 10_000];
+
+fn command_path(index: usize) -> String {
+    let mut nodes: Vec<String> = vec![];
+    let mut rest = index;
+    loop {
+        nodes.push(WORDS[rest % WORDS.len()].to_string());
+        rest /= WORDS.len();
+        if rest == 0 {
+            break;
+        }
+    }
+    nodes.push(format!("cmd{}", index));
+    if index % 3 == 0 {
+        nodes.push("<ARG>".to_string());
+        nodes.push("and".to_string());
+        nodes.push("<ARG>".to_string());
+    }
+
+    nodes.join(" ")
+}
+
+fn build_tree(size: usize) -> PathTree<usize> {
+    let mut tree = PathTree::new();
+    for index in 0..size {
+        tree.set_by_path(index, &command_path(index)).unwrap();
+    }
+    tree
+}
+
+fn bench_lookups(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_by_path");
+    for size in TREE_SIZES.iter() {
+        let tree = build_tree(*size);
+        let path = command_path(size - 2);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &path, |b, path| {
+            b.iter(|| tree.get_by_path(black_box(path)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_argument_matching(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_command_and_args_from_path");
+    for size in TREE_SIZES.iter() {
+        let tree = build_tree(*size);
+        let index = (size - 1) / 3 * 3;
+        let input = command_path(index)
+            .replacen("<ARG>", "first", 1)
+            .replacen("<ARG>", "second", 1);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &input, |b, input| {
+            b.iter(|| tree.get_command_and_args_from_path(black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_insertion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("set_by_path");
+    group.sample_size(10);
+    for size in TREE_SIZES.iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, size| {
+            b.iter(|| build_tree(black_box(*size)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_prefix_enumeration(c: &mut Criterion) {
+    let mut group = c.benchmark_group("paths");
+    group.sample_size(10);
+    for size in TREE_SIZES.iter() {
+        let tree = build_tree(*size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &tree, |b, tree| {
+            b.iter(|| tree.paths().len())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_lookups,
+    bench_argument_matching,
+    bench_insertion,
+    bench_prefix_enumeration
+);
+criterion_main!(benches);
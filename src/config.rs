@@ -4,14 +4,36 @@ const VIOLET_UNKNOWN: &str = "???";
 const VIOLET_VERSION: Option<&'static str> = option_env!("CARGO_PKG_VERSION");
 const VIOLET_AUTHOR: Option<&'static str> = option_env!("CARGO_PKG_AUTHORS");
 const VIOLET_PROMPT: &str = "<<VIO>>";
+const VIOLET_CONTINUATION_PROMPT: &str = "...";
 const VIOLET_NAME: Option<&'static str> = option_env!("CARGO_PKG_NAME");
-const VIOLET_EXIT_MESSAGE: &str = "Bye! AYAYA ^_^";
-const VIOLET_CONFIG_FILE_NAME: &str = "./config.json";
+const VIOLET_CONFIG_FILE_NAME: &str = "config.json";
+const VIOLET_SYNONYMS_FILE_NAME: &str = "synonyms.json";
+const VIOLET_USAGE: &str = "Usage:
+    violet [OPTIONS]
+    violet docs [DIRECTORY]
 
-const ARGSPEC_MISUSED_ERROR_MESSAGE: &str =
-        "ERROR: <ARG> specifier used in a command directly as an argument!\n\nNOTE: please specify an actual argument instead.\nPassing <ARG> as a single self-contained argument without quotation marks (like this: please say <ARG> and <ARG>) to a command is considered a mistake on the user's side.\nExample: instead of\n<<VIO>> explain command <ARG>\n  please use\n<<VIO>> explain command help\n";
+Options:
+    -c, --command <COMMAND>  run COMMAND and exit; repeat to run several commands in order
+    -q, --quiet              don't print the banner or INFO lines
+    -y, --yes                answer \"yes\" to every confirmation question
+        --output <MODE>      print \"text\" (the default) or one \"json\" line per command
+        --no-color           don't colour the output
+    -V, --version            print the version and exit
+    -h, --help               print this message and exit
 
-const VIOLET_HELP_MESSAGE: &str = "\n===HELP MESSAGE START===\nViolet is a command interpreter.
+With -c, the commands run in order until one of them fails, and the exit code is 0 only if they all succeeded.";
+
+pub struct HelpTopic {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub text: &'static str,
+}
+
+const VIOLET_HELP_TOPICS: &[HelpTopic] = &[
+    HelpTopic {
+        name: "basics",
+        summary: "what Violet is and how to get around",
+        text: "Violet is a command interpreter.
 When you see the \"<<VIO>> \" prompt, it means you can enter your command and press <ENTER>.
 ---
 Violet is going to try to interpret that command or let you know if it doesn't know such a command.
@@ -31,9 +53,12 @@ If you want to see the explanation for an individual command:
 or
 <<VIO>> [eca] <ARG>
 If the command is a multi-node command (not just \"exit\", but i.e. \"what is your name\"), take care to enclose it in the \" quotation marks, like so:
-<<VIO>> explain command \"what is your name\"
----
-Violet has some commands with arguments. For example, the
+<<VIO>> explain command \"what is your name\"",
+    },
+    HelpTopic {
+        name: "arguments",
+        summary: "passing single word and multi word arguments to commands",
+        text: "Violet has some commands with arguments. For example, the
 <<VIO>> please say <ARG> and <ARG>
 command just echoes the two arguments back to you.
 ---
@@ -47,7 +72,31 @@ Example of using multi word arguments:
 <<VIO>> please say \"one argument\" and \"another argument\"
 Gotcha! Saying one argument and another argument!
 ---
-All Violet commands are \"paths\" consisting of \"nodes\" separated by spaces.
+Long commands can go over several lines. End a line with \\ to continue the command on the next one, or leave a quote open and close it on a later line:
+<<VIO>> please say \"one argument\" \\
+    ... and \"another
+    ... argument\"
+Gotcha! Saying one argument and another argument!",
+    },
+    HelpTopic {
+        name: "matching",
+        summary: "how Violet tolerates capital letters, punctuation, filler words and synonyms",
+        text: "Violet doesn't mind capital letters, trailing punctuation or the filler words \"please\" and \"the\" in command words, so these all work the same:
+<<VIO>> what time is it
+<<VIO>> What time is it?
+<<VIO>> Please, what time is it?
+Arguments are always passed to commands exactly as you typed them.
+---
+Violet also knows synonyms for some command words, for example \"clock\" for \"time\" or \"delete\" for \"remove\":
+<<VIO>> what clock is it
+<<VIO>> delete alias shutdown
+You can see all of them with \"list synonyms\" and teach Violet new ones:
+<<VIO>> add synonym hour for word time",
+    },
+    HelpTopic {
+        name: "paths",
+        summary: "commands as paths of nodes",
+        text: "All Violet commands are \"paths\" consisting of \"nodes\" separated by spaces.
 Example:
 what is your name => This is a \"path\".
 what, what is, what is your, what is your name => those are all \"nodes\".
@@ -62,9 +111,26 @@ Violet creates the following nodes:
 - this is an alias for => null node
 - this is an alias for exit => active node, with value \"exit\"
 Null nodes are nodes that already exist and have path (such as \"this\" in the example), but their value is null (no value).
-Active nodes are nodes that exist, have value (such as \"this is an alias for exit\" that has value \"exit\"), and play an active role in Violet's operation. For example, from the point of adding the above active node, every time you invoke path \"this is an alias for exit\", Violet will invoke the \"exit\" command.
+Active nodes are nodes that exist, have value (such as \"this is an alias for exit\" that has value \"exit\"), and play an active role in Violet's operation. For example, from the point of adding the above active node, every time you invoke path \"this is an alias for exit\", Violet will invoke the \"exit\" command.",
+    },
+    HelpTopic {
+        name: "aliases",
+        summary: "giving built-in commands your own names",
+        text: "You can give any built-in command your own name with an alias:
+<<VIO>> add alias shutdown for builtin exit
+<<VIO>> shutdown
+Bye! AYAYA ^_^
 ---
-Violet has a command shortcut syntax. It works in the following way.
+Aliases for commands with arguments need the same number of <ARG>s:
+<<VIO>> add alias \"repeat <ARG> <ARG>\" for builtin \"please say <ARG> and <ARG>\"
+---
+You can see your aliases with \"list aliases\" and remove them with \"remove alias <ARG>\".
+Your aliases are saved in the config file when you exit.",
+    },
+    HelpTopic {
+        name: "shortcuts",
+        summary: "the [x] shortcut syntax for commands",
+        text: "Violet has a command shortcut syntax. It works in the following way.
 The shortcut command itself is enclosed in [ and ].
 The letters for the command are first letters of each node in the original command.
 The letter for <ARG> is a.
@@ -88,7 +154,17 @@ Shortcut for
 is
 <<VIO>> [raa] \"alias for exit\"
 ---
-If the same shortcut is created several times due to name collision, the second and subsequent shortcuts are appended with a sequential number.
+Every built-in command declares its own shortcut, so shortcuts stay the same when new commands are added.
+For example, \"remove synonym <ARG>\" is [rsa] and \"remove shortcut <ARG>\" is [rsha], even though their first letters are the same.
+You can see which commands share the same first letters with \"list shortcut collisions\".
+---
+You can also set your own shortcuts with \"set shortcut <ARG> for <ARG>\" and remove them with \"remove shortcut <ARG>\":
+<<VIO>> set shortcut [t] for \"what time is it\"
+<<VIO>> [t]
+Your shortcuts are saved in the config file together with your aliases.
+---
+If a command doesn't declare a shortcut, one is derived from its first letters.
+If the same derived shortcut is created several times due to name collision, the second and subsequent shortcuts are appended with a sequential number.
 Example:
 Assume we have three \"[eaa] <ARG>\" commands, and they were added in this order:
 1. enable alias <ARG>
@@ -107,107 +183,638 @@ The aliases created are going to be:
 - [eaa] <ARG> <ARG> // for eat <ARG> <ARG>
 NOTE: there are no sequential numbers like [eaa2] here. The reason is the different arities of the two commands.
 ---
-You can see what a certain shortcut expands to with \"explain command <ARG>\":
-Example: if you have two commands with the [eca] shortcut name, \"explain command <ARG>\" and \"eat candy <ARG>\"
+You can see which command a shortcut stands for with \"explain command <ARG>\":
 <<VIO>> explain command \"[eca] <ARG>\"
   [[help for explain command <ARG>]]
-<<VIO>> explain command \"[eca2] <ARG>\"
-  [[help for eat candy <ARG>]]
 ---
 You can of course use shortcuts while meta-asking for explanation about explanation:
 <<VIO>> [eca] \"[eca] <ARG>\"
-  [[help for explain command <ARG>]]
-\n===HELP MESSAGE END===\n";
+  [[help for explain command <ARG>]]",
+    },
+];
+
+pub struct CommandHelp {
+    pub summary: &'static str,
+    pub arguments: &'static [&'static str],
+    pub examples: &'static [&'static str],
+    pub notes: &'static [&'static str],
+    pub related: &'static [&'static str],
+}
+
+impl CommandHelp {
+    pub fn render(&self, path: &str, shortcuts: &[String]) -> String {
+        let mut rendered = format!("{} {}\n  {}\n", VIOLET_PROMPT, path, self.summary);
+        if !shortcuts.is_empty() {
+            rendered.push_str(&format!("  Shortcut: {}\n", shortcuts.join(", ")));
+        }
+        if !self.arguments.is_empty() {
+            rendered.push_str("---\nArguments:\n");
+            for (index, argument) in self.arguments.iter().enumerate() {
+                rendered.push_str(&format!("  <ARG> {}: {}\n", index + 1, argument));
+            }
+        }
+        for (index, example) in self.examples.iter().enumerate() {
+            rendered.push_str(&format!("---\nExample {}:\n{}\n", index + 1, example));
+        }
+        if !self.notes.is_empty() {
+            rendered.push_str("---\n");
+            for (index, note) in self.notes.iter().enumerate() {
+                rendered.push_str(&format!("NOTE {}: {}\n", index + 1, note));
+            }
+        }
+        if !self.related.is_empty() {
+            rendered.push_str(&format!("---\nSee also: {}\n", self.related.join(", ")));
+        }
+
+        rendered
+    }
+}
 
 pub struct Help;
 impl Help {
-    pub fn exit() -> &'static str {
-        "<<VIO>> exit
-          Exits Violet and saves Violet's data correctly.
-        ---
-        WARNING: if you don't want Violet's data (such as aliases) to be lost between sessions, please always take care to exit via the exit command."
-    }
-
-    pub fn what_is_your_name() -> &'static str {
-        "<<VIO>> what is your name
-          Makes Violet give you a short and cute introduction! ^_^
-        "
-    }
-
-    pub fn what_time_is_it() -> &'static str {
-        "<<VIO>> what time is it
-          Violet tells you what time it is according to your system clock.
-        ---
-        NOTE: this doesn't fetch current time from the web. If your system clock is incorrect, then you're going to get the incorrect time.
-        "
-    }
-
-    pub fn please_say_arg_and_arg() -> &'static str {
-        "<<VIO>> please say <ARG> and <ARG>
-          This command just echoes the two arguments you provide back at you.
-        ---
-        Example:
-        <<VIO>> please say one and two
-        Gotcha! Saying one and two!
-        Example 2:
-        <<VIO>> please say \"this is arg\" and \"this is also arg\"
-        Gotcha! Saying this is arg and this is also arg!
-        "
-    }
-
-    pub fn add_alias_arg_for_builtin_arg() -> &'static str {
-        "<<VIO>> add alias <ARG> for builtin <ARG>
-          Adds a new alias for an existing built-in command so that the new alias would invoke the same command as the builtin when called.
-        ---
-        Example 1:
-        <<VIO>> add alias shutdown for builtin exit
-        <<VIO>> shutdown
-        Bye! AYAYA ^_^
-        ---
-        Example 2:
-        <<VIO>> add alias \"alias <ARG> for <ARG>\" for builtin \"add alias <ARG> for builtin <ARG>\"
-        <<VIO>> alias \"blow up\" for \"exit\"
-        <<VIO>> blow up
-        Bye! AYAYA ^_^
-        ---
-        NOTE 1: You can't set an alias if an identical one already exists.
-        NOTE 2: You can't set an alias to a name identical to an existing built-in command.
-        NOTE 3: The alias and the builtin have to have the same amount of <ARG>s, otherwise the alias won't be set successfully.
-        NOTE 4: You can set an alias for both add alias and remove alias commands.
-        "
-    }
-
-    pub fn remove_alias_arg() -> &'static str {
-        "<<VIO>> remove alias <ARG>
-          Removes an existing alias.
-        ---
-        Example:
-        <<VIO>> add alias \"shutdown\" for builtin \"exit\"
-        <<VIO>> remove alias \"shutdown\"
-        <<VIO>> shutdown
-        shutdown: command does not exist.
-        ---
-        NOTE 1: You obviously cannot remove an alias which doesn't exist.
-        NOTE 2: If you try to invoke this with a builtin as an argument, Violet will explicitly tell you that you can't remove builtins.
-        "
-    }
-
-    pub fn list_available_commands() -> &'static str {
-        "<<VIO>> list available commands
-          lists all the currently available built-in commands.
-        "
-    }
-
-    pub fn explain_command_arg() -> &'static str {
-        "<<VIO>> explain command <ARG>
-          The command currently being invoked. Explains the command specified in the <ARG>.
-        "
-    }
-
-    pub fn help() -> &'static str {
-        "<<VIO>> help
-          A concise yet information-dense intro to the basics of Violet.
-        "
+    pub fn exit() -> CommandHelp {
+        CommandHelp {
+            summary: "Exits Violet and saves Violet's data correctly.",
+            arguments: &[],
+            examples: &[],
+            notes: &["if you don't want Violet's data (such as aliases) to be lost between sessions, please always take care to exit via the exit command."],
+            related: &[],
+        }
+    }
+
+    pub fn what_is_your_name() -> CommandHelp {
+        CommandHelp {
+            summary: "Makes Violet give you a short and cute introduction! ^_^",
+            arguments: &[],
+            examples: &[],
+            notes: &[],
+            related: &[],
+        }
+    }
+
+    pub fn what_time_is_it() -> CommandHelp {
+        CommandHelp {
+            summary: "Violet tells you what time it is according to your system clock.",
+            arguments: &[],
+            examples: &[],
+            notes: &["this doesn't fetch current time from the web. If your system clock is incorrect, then you're going to get the incorrect time."],
+            related: &["what time is it in <ARG>", "what is the date"],
+        }
+    }
+
+    pub fn what_time_is_it_in_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Violet tells you what time it is somewhere else in the world.",
+            arguments: &["a city like \"Tokyo\" or \"New York\", a time zone like \"Europe/Kyiv\", or UTC"],
+            examples: &["<<VIO>> what time is it in Kyiv\nIt's 04:20 PM in Europe/Kyiv (EEST, UTC+03:00)."],
+            notes: &["Violet knows the cities that name a time zone in the tz database, so \"Lviv\" won't work, but \"Kyiv\" will."],
+            related: &["what time is it"],
+        }
+    }
+
+    pub fn what_is_the_date() -> CommandHelp {
+        CommandHelp {
+            summary: "Violet tells you today's date according to your system clock.",
+            arguments: &[],
+            examples: &["<<VIO>> what is the date\nToday is Monday, October 19, 2026."],
+            notes: &[],
+            related: &["what time is it", "how long until <ARG>"],
+        }
+    }
+
+    pub fn start_stopwatch() -> CommandHelp {
+        CommandHelp {
+            summary: "Starts a stopwatch that measures how long it takes you to do something.",
+            arguments: &[],
+            examples: &["<<VIO>> start stopwatch\nThe stopwatch is running! Run \"stop stopwatch\" to stop it."],
+            notes: &["There's only one stopwatch, so starting it again while it's running is an error."],
+            related: &["stop stopwatch"],
+        }
+    }
+
+    pub fn stop_stopwatch() -> CommandHelp {
+        CommandHelp {
+            summary:
+                "Stops the stopwatch and tells you how much time has passed since it was started.",
+            arguments: &[],
+            examples: &["<<VIO>> stop stopwatch\nThe stopwatch stopped at 1 minute and 5 seconds."],
+            notes: &[],
+            related: &["start stopwatch"],
+        }
+    }
+
+    pub fn remind_me_in_arg_to_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Reminds you about something after some time, even while you're typing another command.",
+            arguments: &["how long to wait, like \"5 minutes\", \"1h30m\" or \"an hour and 15 minutes\"", "what to remind you about"],
+            examples: &["<<VIO>> remind me in \"10 minutes\" to \"check the oven\"\nI'll remind you to check the oven at 04:30:00 PM.\n...\nNOTICE: Reminder: check the oven!"],
+            notes: &["Reminders only last while Violet is running, they are forgotten when you exit."],
+            related: &["how long until <ARG>"],
+        }
+    }
+
+    pub fn how_long_until_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Violet tells you how much time is left until a moment.",
+            arguments: &["a time like \"17:30\" or \"5pm\", a date like \"2030-12-31\" (optionally followed by a time), \"tomorrow\", \"christmas\" or \"new year\""],
+            examples: &["<<VIO>> how long until 5pm\nMonday, October 19, 2026 at 05:00 PM is in 40 minutes.", "<<VIO>> how long until \"new year\""],
+            notes: &["A time of day that has already passed today means that time tomorrow."],
+            related: &["what is the date", "remind me in <ARG> to <ARG>"],
+        }
+    }
+
+    pub fn please_say_arg_and_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "This command just echoes the two arguments you provide back at you.",
+            arguments: &["the first thing to say", "the second thing to say"],
+            examples: &[
+                "<<VIO>> please say one and two\nGotcha! Saying one and two!",
+                "<<VIO>> please say \"this is arg\" and \"this is also arg\"\nGotcha! Saying this is arg and this is also arg!",
+            ],
+            notes: &[],
+            related: &[],
+        }
+    }
+
+    pub fn add_alias_arg_for_builtin_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Adds a new alias for an existing built-in command so that the new alias would invoke the same command as the builtin when called.",
+            arguments: &["the alias to add", "the built-in command the alias invokes"],
+            examples: &[
+                "<<VIO>> add alias shutdown for builtin exit\n<<VIO>> shutdown\nBye! AYAYA ^_^",
+                "<<VIO>> add alias \"alias <ARG> for <ARG>\" for builtin \"add alias <ARG> for builtin <ARG>\"\n<<VIO>> alias \"blow up\" for \"exit\"\n<<VIO>> blow up\nBye! AYAYA ^_^",
+            ],
+            notes: &[
+                "You can't set an alias if an identical one already exists.",
+                "You can't set an alias to a name identical to an existing built-in command.",
+                "The alias and the builtin have to have the same amount of <ARG>s, otherwise the alias won't be set successfully.",
+                "You can set an alias for both add alias and remove alias commands.",
+            ],
+            related: &["remove alias <ARG>", "list aliases"],
+        }
+    }
+
+    pub fn remove_alias_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Removes an existing alias.",
+            arguments: &["the alias to remove"],
+            examples: &["<<VIO>> add alias \"shutdown\" for builtin \"exit\"\n<<VIO>> remove alias \"shutdown\"\nRemove alias shutdown? (yes/no)\n<<VIO>> yes\nRemoved alias shutdown.\n<<VIO>> shutdown\nERROR: shutdown: command does not exist."],
+            notes: &[
+                "You obviously cannot remove an alias which doesn't exist.",
+                "If you try to invoke this with a builtin as an argument, Violet will explicitly tell you that you can't remove builtins.",
+                "Violet asks before removing the alias, unless confirmations are off.",
+            ],
+            related: &["add alias <ARG> for builtin <ARG>", "list aliases"],
+        }
+    }
+
+    pub fn list_available_commands() -> CommandHelp {
+        CommandHelp {
+            summary: "Lists all the currently available built-in commands, grouped by category, with their shortcuts and number of arguments.",
+            arguments: &[],
+            examples: &[],
+            notes: &[],
+            related: &["list available commands matching <ARG>", "explain command <ARG>"],
+        }
+    }
+
+    pub fn list_available_commands_matching_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Lists the available built-in commands which contain <ARG> in their name or shortcut.",
+            arguments: &["the text to look for"],
+            examples: &["<<VIO>> list available commands matching alias"],
+            notes: &[],
+            related: &["list available commands"],
+        }
+    }
+
+    pub fn list_aliases() -> CommandHelp {
+        CommandHelp {
+            summary:
+                "Lists all the aliases you've added, together with the built-in commands they call.",
+            arguments: &[],
+            examples: &[],
+            notes: &[],
+            related: &["add alias <ARG> for builtin <ARG>", "remove alias <ARG>"],
+        }
+    }
+
+    pub fn explain_command_arg() -> CommandHelp {
+        CommandHelp {
+            summary:
+                "The command currently being invoked. Explains the command specified in the <ARG>.",
+            arguments: &["the command to explain"],
+            examples: &[
+                "<<VIO>> explain command \"what is your name\"",
+                "<<VIO>> explain command \"please say hi and bye\"\n\"please say hi and bye\" runs [please say <ARG> and <ARG>] with the arguments: \"hi\", \"bye\".",
+                "<<VIO>> explain command [wtii]\n[wtii] is a shortcut for [what time is it].",
+            ],
+            notes: &["You can explain aliases, shortcuts and commands with actual arguments too, Violet shows which command they run before explaining it."],
+            related: &["help topic <ARG>", "list available commands"],
+        }
+    }
+
+    pub fn add_synonym_arg_for_word_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Makes Violet understand a new word in place of a word used in the built-in commands.",
+            arguments: &["the new word", "the command word it stands for"],
+            examples: &["<<VIO>> add synonym hour for word time\n<<VIO>> what hour is it\nYour system clock says it's 04:20 PM now!"],
+            notes: &[
+                "both the synonym and the word have to be single words.",
+                "the word has to be used in at least one built-in command.",
+                "the synonym can't be a word that built-in commands already use, or an existing synonym.",
+                "synonyms are saved to the synonyms.json file when you exit, which you can also edit by hand.",
+            ],
+            related: &["remove synonym <ARG>", "list synonyms"],
+        }
+    }
+
+    pub fn remove_synonym_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Removes an existing synonym, so Violet stops understanding it as a command word.",
+            arguments: &["the synonym to remove"],
+            examples: &["<<VIO>> remove synonym clock\n<<VIO>> what clock is it\nERROR: what clock is it: command does not exist."],
+            notes: &[],
+            related: &["add synonym <ARG> for word <ARG>", "list synonyms"],
+        }
+    }
+
+    pub fn list_synonyms() -> CommandHelp {
+        CommandHelp {
+            summary: "Lists all the synonyms Violet currently knows, grouped by the command word they stand for.",
+            arguments: &[],
+            examples: &[],
+            notes: &[],
+            related: &["add synonym <ARG> for word <ARG>", "remove synonym <ARG>"],
+        }
+    }
+
+    pub fn set_shortcut_arg_for_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Sets your own shortcut for an existing built-in command.",
+            arguments: &["the shortcut, a single word enclosed in [ and ]", "the built-in command the shortcut invokes"],
+            examples: &[
+                "<<VIO>> set shortcut [t] for \"what time is it\"\n<<VIO>> [t]\nYour system clock says it's 04:20 PM now!",
+                "<<VIO>> set shortcut [say] for \"please say <ARG> and <ARG>\"\n<<VIO>> [say] one two\nGotcha. Saying one and two!",
+            ],
+            notes: &[
+                "You don't need to add <ARG>s to the shortcut, Violet adds as many as the command needs.",
+                "you can't set a shortcut that's already taken by another command.",
+                "your shortcuts are saved in the config file when you exit.",
+            ],
+            related: &["remove shortcut <ARG>", "list shortcut collisions"],
+        }
+    }
+
+    pub fn remove_shortcut_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Removes a shortcut you've set with set shortcut.",
+            arguments: &["the shortcut to remove"],
+            examples: &[
+                "<<VIO>> remove shortcut [t]\n<<VIO>> [t]\nERROR: [t]: command does not exist.",
+            ],
+            notes: &["the shortcuts built into Violet can't be removed."],
+            related: &["set shortcut <ARG> for <ARG>"],
+        }
+    }
+
+    pub fn list_shortcut_collisions() -> CommandHelp {
+        CommandHelp {
+            summary: "Lists the commands whose first letters would produce the same shortcut, together with the shortcuts they actually use.",
+            arguments: &[],
+            examples: &[],
+            notes: &[],
+            related: &["set shortcut <ARG> for <ARG>"],
+        }
+    }
+
+    pub fn generate_documentation_to_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Writes a Markdown reference (violet.md) and a man page (violet.1) for all the built-in commands into a folder.",
+            arguments: &["the folder to write the documentation to"],
+            examples: &["<<VIO>> generate documentation to docs\nINFO: wrote docs/violet.md\nINFO: wrote docs/violet.1"],
+            notes: &[
+                "The folder is created if it doesn't exist yet, and existing documentation files in it are overwritten.",
+                "You can also generate the documentation without starting the interpreter by running \"violet docs <folder>\".",
+            ],
+            related: &["help topic <ARG>", "list available commands"],
+        }
+    }
+
+    pub fn switch_language_to_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Switches the language Violet talks in and adds the command names of that language.",
+            arguments: &["the language, either its code (en, uk) or its name"],
+            examples: &["<<VIO>> switch language to uk\nINFO: тепер я розмовляю мовою: українська.\n<<VIO>> котра година\nYour system clock says it's 04:20 PM now!"],
+            notes: &[
+                "The English command names always keep working, whatever the language.",
+                "By default Violet picks the language from the LANG environment variable. The language you switch to is saved in the config file and wins over LANG.",
+                "Messages which haven't been translated yet are shown in English.",
+            ],
+            related: &["list available commands"],
+        }
+    }
+
+    pub fn set_prompt_to_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Changes the prompt Violet shows when it waits for your command.",
+            arguments: &["the prompt template, or \"default\" to bring back <<VIO>>"],
+            examples: &[
+                "<<VIO>> set prompt to \"violet>\"\nviolet> ",
+                "<<VIO>> set prompt to \"[{time}] {status} {cwd} >\"\n[16:20] 0 /home/me >",
+            ],
+            notes: &[
+                "The prompt can use these variables: {time}, {status} (0 if the last command succeeded, 1 if it failed), {aliases} (how many aliases you have), {cwd}, {result} (the result of the last calculation), {name} and {version}.",
+                "The prompt is saved in the config file when you exit.",
+            ],
+            related: &["set theme to <ARG>", "set banner to <ARG>"],
+        }
+    }
+
+    pub fn set_banner_to_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Changes the greeting Violet shows when it starts.",
+            arguments: &["the banner template, or \"default\" to bring back the usual greeting"],
+            examples: &["<<VIO>> set banner to \"Hi! {name} {version} is ready.\"\nHi! Violet 0.2.0 is ready."],
+            notes: &["The banner can use the same variables as the prompt, see \"explain command \\\"set prompt to <ARG>\\\"\"."],
+            related: &["set prompt to <ARG>", "set exit message to <ARG>"],
+        }
+    }
+
+    pub fn set_exit_message_to_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Changes what Violet says when you exit.",
+            arguments: &["the exit message template, or \"default\" to bring back the usual goodbye"],
+            examples: &["<<VIO>> set exit message to \"See you at {time}!\"\n<<VIO>> exit\nSee you at 16:20!"],
+            notes: &["The exit message can use the same variables as the prompt."],
+            related: &["set banner to <ARG>", "exit"],
+        }
+    }
+
+    pub fn set_theme_to_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Colours the prompt, banner and exit message with a theme: plain, violet, ocean or forest.",
+            arguments: &["the theme name, or \"default\" for plain"],
+            examples: &["<<VIO>> set theme to violet"],
+            notes: &["Colours are only used when Violet talks to a terminal and the NO_COLOR environment variable isn't set, so piping Violet's output stays plain."],
+            related: &["set prompt to <ARG>"],
+        }
+    }
+
+    pub fn remove_config_file() -> CommandHelp {
+        CommandHelp {
+            summary: "Deletes the config file once you've removed all the aliases, shortcuts and settings in it.",
+            arguments: &[],
+            examples: &["<<VIO>> remove config file\nDelete the config file config.json? (yes/no)\n<<VIO>> yes\nDeleted config.json."],
+            notes: &["Violet never deletes the config file on its own. When everything in it has been removed, it saves the config empty until you delete it with this command.", "Violet asks before deleting it, unless confirmations are off."],
+            related: &["remove alias <ARG>", "set confirmations to <ARG>"],
+        }
+    }
+
+    pub fn set_confirmations_to_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Turns the yes/no questions before destructive commands on or off for this session.",
+            arguments: &["\"on\" to be asked, or \"off\" to go ahead without asking"],
+            examples: &["<<VIO>> set confirmations to off\nConfirmations are off: destructive commands won't ask before they run."],
+            notes: &["Scripts can start with this command instead of answering every question. Running Violet with --yes does the same.", "The setting isn't saved, every session starts with confirmations on."],
+            related: &["remove alias <ARG>", "copy file <ARG> to <ARG>"],
+        }
+    }
+
+    pub fn undo_last_change() -> CommandHelp {
+        CommandHelp {
+            summary: "Takes back the last change to your aliases, shortcuts or synonyms.",
+            arguments: &[],
+            examples: &["<<VIO>> remove alias bye\nRemove alias bye? (yes/no)\n<<VIO>> yes\nRemoved alias bye.\n<<VIO>> undo last change\nUndid the last change: removed alias [bye] for [exit]."],
+            notes: &["Run it again to take back the change before that. Violet remembers the last 100 changes of the session."],
+            related: &["redo last change", "show change log"],
+        }
+    }
+
+    pub fn redo_last_change() -> CommandHelp {
+        CommandHelp {
+            summary: "Makes the last change you undid again.",
+            arguments: &[],
+            examples: &["<<VIO>> redo last change\nRedid the change: removed alias [bye] for [exit]."],
+            notes: &["Making a new change forgets the changes you undid, so they can't be redone any more."],
+            related: &["undo last change", "show change log"],
+        }
+    }
+
+    pub fn show_change_log() -> CommandHelp {
+        CommandHelp {
+            summary: "Lists the changes to your aliases, shortcuts and synonyms made in this session, the latest first.",
+            arguments: &[],
+            examples: &["<<VIO>> show change log\nChanges in this session, the latest first:\n\n- removed alias [bye] for [exit];\n- added alias [bye] for [exit];"],
+            notes: &["Changes you undid are listed separately, as the ones \"redo last change\" can bring back."],
+            related: &["undo last change", "redo last change"],
+        }
+    }
+
+    pub fn start_recording_to_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Records everything you type and everything Violet answers into a transcript file.",
+            arguments: &["the transcript file, relative to the current folder"],
+            examples: &["<<VIO>> start recording to bug.jsonl\nRecording to /home/me/bug.jsonl. Run \"stop recording\" when you're done."],
+            notes: &["Every line of the transcript is a JSON object with the time, the input, the command it ran with its arguments, whether it succeeded and all the messages Violet printed.", "Violet asks before replacing a file that already exists."],
+            related: &["stop recording", "replay session <ARG>"],
+        }
+    }
+
+    pub fn stop_recording() -> CommandHelp {
+        CommandHelp {
+            summary: "Stops the recording started with \"start recording to <ARG>\" and closes the transcript.",
+            arguments: &[],
+            examples: &["<<VIO>> stop recording\nStopped recording, 3 commands saved to /home/me/bug.jsonl."],
+            notes: &["The recording also stops when Violet exits."],
+            related: &["start recording to <ARG>", "replay session <ARG>"],
+        }
+    }
+
+    pub fn replay_session_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Runs the commands from a transcript again and shows where the output differs from the recording.",
+            arguments: &["the transcript file made by \"start recording to <ARG>\""],
+            examples: &["<<VIO>> replay session bug.jsonl\nReplayed 3 commands from bug.jsonl, and the output matches the recording.", "<<VIO>> replay session clock.jsonl\nCommand 1, \"what time is it\", differs:\n- Your system clock says it's 10:00 AM now!\n+ Your system clock says it's 10:05 AM now!\nERROR: the replay differs from the recording in 1 of 1 commands."],
+            notes: &["The replayed commands really run, so aliases they add stay added.", "Lines starting with - are from the recording and lines starting with + are from the replay.", "Job output, exit and the recording commands themselves are skipped."],
+            related: &["start recording to <ARG>", "stop recording"],
+        }
+    }
+
+    pub fn run_arg_in_background() -> CommandHelp {
+        CommandHelp {
+            summary: "Runs a Violet command in the background, so you can keep typing while it runs.",
+            arguments: &["the command to run, in quotation marks if it has several words"],
+            examples: &["<<VIO>> run \"what time is it\" in background\nStarted job 1: run \"what time is it\" in background.\nNOTICE: job 1 runs \"what time is it\":\nYour system clock says it's 04:20 PM now!"],
+            notes: &["Background jobs can't exit Violet or start other jobs."],
+            related: &["list jobs", "every <ARG> run <ARG>"],
+        }
+    }
+
+    pub fn every_arg_run_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Runs a Violet command over and over, waiting the given time in between.",
+            arguments: &["how long to wait between runs, like \"5 minutes\" or 1h30m", "the command to run, in quotation marks if it has several words"],
+            examples: &["<<VIO>> every \"15 minutes\" run \"what time is it\"\nStarted job 2: every 15 minutes run \"what time is it\", next at 04:35:00 PM."],
+            notes: &["Jobs only last while Violet is running, they are forgotten when you exit.", "The first run happens after the first wait, not right away."],
+            related: &["cancel job <ARG>", "at <ARG> run <ARG>"],
+        }
+    }
+
+    pub fn at_arg_run_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Runs a Violet command once, at the given moment.",
+            arguments: &[
+                "when to run the command, like \"17:30\", \"5pm\" or \"2030-12-31 23:59\"",
+                "the command to run, in quotation marks if it has several words",
+            ],
+            examples: &["<<VIO>> at 5pm run \"please say tea and biscuits\""],
+            notes: &["A time of day that has already passed today means that time tomorrow."],
+            related: &["list jobs", "how long until <ARG>"],
+        }
+    }
+
+    pub fn list_jobs() -> CommandHelp {
+        CommandHelp {
+            summary: "Lists the background and scheduled jobs that haven't finished yet, with their numbers.",
+            arguments: &[],
+            examples: &["<<VIO>> list jobs\nYour jobs:\n\n- job 2: every 15 minutes run \"what time is it\", next at 04:35:00 PM;"],
+            notes: &[],
+            related: &["cancel job <ARG>"],
+        }
+    }
+
+    pub fn cancel_job_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Cancels a job, so it doesn't run again.",
+            arguments: &["the job's number, as shown by \"list jobs\""],
+            examples: &["<<VIO>> cancel job 2\nCancelled job 2: every 15 minutes run \"what time is it\", next at 04:35:00 PM."],
+            notes: &["A job that is running right now finishes its current run."],
+            related: &["list jobs"],
+        }
+    }
+
+    pub fn calculate_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Violet calculates an arithmetic expression, with +, -, *, /, % and ^ (or **), parentheses and numbers written in digits or in English words.",
+            arguments: &["the expression to calculate, quoted if it has spaces"],
+            examples: &["<<VIO>> calculate \"(2 + 3) * 4.5\"\n(2 + 3) * 4.5 = 22.5", "<<VIO>> calculate \"twenty one times two\"\ntwenty one times two = 42", "<<VIO>> calculate \"ans / 4\"\nans / 4 = 10.5"],
+            notes: &["Words like \"plus\", \"minus\", \"times\", \"divided by\", \"modulo\", \"to the power of\" and \"squared\" work as operations.", "\"ans\" (or \"result\") is the result of the last calculation, \"pi\" and \"e\" are the constants.", "Whole numbers stay exact. If a result doesn't fit into a whole number Violet says it's too big instead of guessing."],
+            related: &["what is <ARG> plus <ARG>", "what is <ARG> times <ARG>"],
+        }
+    }
+
+    pub fn what_is_arg_plus_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Violet adds two numbers together.",
+            arguments: &["the first number", "the second number"],
+            examples: &["<<VIO>> what is 2 plus 2\n2 + 2 = 4"],
+            notes: &["Numbers can be written in words too, quote them if they have several: what is \"twenty one\" plus one."],
+            related: &["what is <ARG> minus <ARG>", "calculate <ARG>"],
+        }
+    }
+
+    pub fn what_is_arg_minus_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Violet subtracts the second number from the first one.",
+            arguments: &["the number to subtract from", "the number to subtract"],
+            examples: &["<<VIO>> what is ten minus 12.5\nten - 12.5 = -2.5"],
+            notes: &[],
+            related: &["what is <ARG> plus <ARG>", "calculate <ARG>"],
+        }
+    }
+
+    pub fn what_is_arg_times_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Violet multiplies two numbers.",
+            arguments: &["the first number", "the second number"],
+            examples: &["<<VIO>> what is three times seven\nthree * seven = 21"],
+            notes: &[],
+            related: &["what is <ARG> divided by <ARG>", "calculate <ARG>"],
+        }
+    }
+
+    pub fn what_is_arg_divided_by_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Violet divides the first number by the second one.",
+            arguments: &["the number to divide", "the number to divide by"],
+            examples: &["<<VIO>> what is 7 divided by 2\n7 / 2 = 3.5"],
+            notes: &["Dividing by zero is an error, not infinity."],
+            related: &["what is <ARG> times <ARG>", "calculate <ARG>"],
+        }
+    }
+
+    pub fn show_file_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Violet prints the contents of a text file.",
+            arguments: &["the file, relative to the folder you're in or absolute"],
+            examples: &["<<VIO>> show file notes.txt\nbuy tea\ncall mom"],
+            notes: &["Only text files can be shown."],
+            related: &["list files in <ARG>", "where am i"],
+        }
+    }
+
+    pub fn list_files_in_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Lists the files and folders inside a folder, folders first.",
+            arguments: &["the folder, relative to the folder you're in or absolute; \".\" is the folder you're in"],
+            examples: &["<<VIO>> list files in .\nFiles in /home/me/notes:\n\n- old/\n- notes.txt"],
+            notes: &[],
+            related: &["go to folder <ARG>", "show file <ARG>"],
+        }
+    }
+
+    pub fn copy_file_arg_to_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Copies a file to another file or into a folder.",
+            arguments: &["the file to copy", "the new file, or the folder to copy it into"],
+            examples: &["<<VIO>> copy file notes.txt to backup\nCopied notes.txt to /home/me/backup/notes.txt (17 bytes).", "<<VIO>> copy file notes.txt to old.txt\n/home/me/old.txt already exists. Replace it? (yes/no)\n<<VIO>> yes\nCopied notes.txt to /home/me/old.txt (17 bytes)."],
+            notes: &["Violet asks before replacing a file that already exists. Anything other than \"yes\" leaves it alone."],
+            related: &["list files in <ARG>"],
+        }
+    }
+
+    pub fn where_am_i() -> CommandHelp {
+        CommandHelp {
+            summary: "Violet tells you which folder file commands start from.",
+            arguments: &[],
+            examples: &["<<VIO>> where am i\nYou're in /home/me."],
+            notes: &["It's the folder Violet was started in until you use \"go to folder <ARG>\"."],
+            related: &["go to folder <ARG>"],
+        }
+    }
+
+    pub fn go_to_folder_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Changes the folder that file commands start from.",
+            arguments: &["the folder, relative to the folder you're in or absolute; \"..\" goes up and \"~\" is your home folder"],
+            examples: &["<<VIO>> go to folder ..\nYou're in /home now."],
+            notes: &["Violet keeps saving its config files where it was started."],
+            related: &["where am i", "list files in <ARG>"],
+        }
+    }
+
+    pub fn help() -> CommandHelp {
+        CommandHelp {
+            summary: "A concise yet information-dense intro to the basics of Violet, with the list of help topics.",
+            arguments: &[],
+            examples: &[],
+            notes: &[],
+            related: &["help topic <ARG>"],
+        }
+    }
+
+    pub fn help_topic_arg() -> CommandHelp {
+        CommandHelp {
+            summary: "Shows the help for a topic, or for a command if <ARG> is a command.",
+            arguments: &["the topic or command to show help for"],
+            examples: &[
+                "<<VIO>> help topic shortcuts",
+                "<<VIO>> help topic commands",
+            ],
+            notes: &["\"help topic commands\" lists every command with a short summary."],
+            related: &["help", "explain command <ARG>"],
+        }
     }
 }
 
@@ -223,22 +830,64 @@ pub fn get_violet_prompt() -> String {
     format!("{} ", VIOLET_PROMPT)
 }
 
-pub fn get_violet_name() -> String {
-    clone_uppercased(VIOLET_NAME.unwrap_or(VIOLET_UNKNOWN))
+pub fn get_usage_message() -> String {
+    String::from(VIOLET_USAGE)
+}
+
+pub fn get_continuation_prompt() -> String {
+    String::from(VIOLET_CONTINUATION_PROMPT)
+}
+
+pub fn get_prompt_template() -> String {
+    VIOLET_PROMPT.to_string()
 }
 
-pub fn get_exit_message() -> String {
-    VIOLET_EXIT_MESSAGE.to_string()
+pub fn get_violet_name() -> String {
+    clone_uppercased(VIOLET_NAME.unwrap_or(VIOLET_UNKNOWN))
 }
 
 pub fn get_config_file_name() -> String {
     VIOLET_CONFIG_FILE_NAME.to_string()
 }
 
-pub fn get_help_message() -> String {
-    VIOLET_HELP_MESSAGE.to_string()
+pub fn get_synonyms_file_name() -> String {
+    VIOLET_SYNONYMS_FILE_NAME.to_string()
+}
+
+pub fn get_help_topics() -> &'static [HelpTopic] {
+    VIOLET_HELP_TOPICS
+}
+
+pub fn get_help_topic(name: &str) -> Option<&'static HelpTopic> {
+    let name = name.trim().to_lowercase();
+    VIOLET_HELP_TOPICS.iter().find(|topic| topic.name == name)
+}
+
+#[test]
+fn test_rendering_command_help() {
+    let rendered =
+        Help::remove_alias_arg().render("remove alias <ARG>", &[String::from("[raa] <ARG>")]);
+    assert!(rendered.starts_with(
+        "<<VIO>> remove alias <ARG>\n  Removes an existing alias.\n  Shortcut: [raa] <ARG>\n"
+    ));
+    assert!(rendered.contains("Arguments:\n  <ARG> 1: the alias to remove\n"));
+    assert!(rendered.contains("---\nExample 1:\n<<VIO>> add alias"));
+    assert!(rendered.contains("NOTE 2: If you try"));
+    assert!(rendered.ends_with("See also: add alias <ARG> for builtin <ARG>, list aliases\n"));
+
+    let rendered = Help::what_is_your_name().render("what is your name", &[]);
+    assert_eq!(
+        "<<VIO>> what is your name\n  Makes Violet give you a short and cute introduction! ^_^\n",
+        rendered
+    );
 }
 
-pub fn get_argspec_misused_error_message() -> String {
-    ARGSPEC_MISUSED_ERROR_MESSAGE.to_string()
+#[test]
+fn test_finding_help_topics() {
+    assert_eq!(
+        Some("shortcuts"),
+        get_help_topic(" Shortcuts ").map(|topic| topic.name)
+    );
+    assert!(get_help_topic("commands").is_none());
+    assert!(get_help_topic("nonsense").is_none());
 }
@@ -0,0 +1,175 @@
+use crate::util::treepath::TreePath;
+
+const MAX_CHANGES: usize = 100;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    AddAlias {
+        alias: String,
+        for_builtin: String,
+    },
+    RemoveAlias {
+        alias: String,
+        for_builtin: String,
+    },
+    AddSynonym {
+        synonym: String,
+        for_word: String,
+    },
+    RemoveSynonym {
+        synonym: String,
+        for_word: String,
+    },
+    SetShortcut {
+        shortcut: String,
+        for_builtin: String,
+    },
+    RemoveShortcut {
+        shortcut: String,
+        for_builtin: String,
+    },
+}
+
+impl Change {
+    pub fn inverse(&self) -> Change {
+        match self.clone() {
+            Change::AddAlias { alias, for_builtin } => Change::RemoveAlias { alias, for_builtin },
+            Change::RemoveAlias { alias, for_builtin } => Change::AddAlias { alias, for_builtin },
+            Change::AddSynonym { synonym, for_word } => Change::RemoveSynonym { synonym, for_word },
+            Change::RemoveSynonym { synonym, for_word } => Change::AddSynonym { synonym, for_word },
+            Change::SetShortcut {
+                shortcut,
+                for_builtin,
+            } => Change::RemoveShortcut {
+                shortcut,
+                for_builtin,
+            },
+            Change::RemoveShortcut {
+                shortcut,
+                for_builtin,
+            } => Change::SetShortcut {
+                shortcut,
+                for_builtin,
+            },
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Change::AddAlias { alias, for_builtin } => {
+                format!("added alias [{}] for [{}]", alias, for_builtin)
+            }
+            Change::RemoveAlias { alias, for_builtin } => {
+                format!("removed alias [{}] for [{}]", alias, for_builtin)
+            }
+            Change::AddSynonym { synonym, for_word } => {
+                format!("added synonym [{}] for [{}]", synonym, for_word)
+            }
+            Change::RemoveSynonym { synonym, for_word } => {
+                format!("removed synonym [{}] for [{}]", synonym, for_word)
+            }
+            Change::SetShortcut {
+                shortcut,
+                for_builtin,
+            } => format!(
+                "set shortcut {} for [{}]",
+                TreePath::get_shortcut_name(shortcut).unwrap_or_else(|| shortcut.clone()),
+                for_builtin
+            ),
+            Change::RemoveShortcut {
+                shortcut,
+                for_builtin,
+            } => format!(
+                "removed shortcut {} for [{}]",
+                TreePath::get_shortcut_name(shortcut).unwrap_or_else(|| shortcut.clone()),
+                for_builtin
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ChangeLog {
+    done: Vec<Change>,
+    undone: Vec<Change>,
+}
+
+impl ChangeLog {
+    pub fn record(&mut self, change: Change) {
+        self.done.push(change);
+        if self.done.len() > MAX_CHANGES {
+            self.done.remove(0);
+        }
+        self.undone.clear();
+    }
+
+    pub fn to_undo(&self) -> Option<&Change> {
+        self.done.last()
+    }
+
+    pub fn to_redo(&self) -> Option<&Change> {
+        self.undone.last()
+    }
+
+    pub fn mark_undone(&mut self) {
+        if let Some(change) = self.done.pop() {
+            self.undone.push(change);
+        }
+    }
+
+    pub fn mark_redone(&mut self) {
+        if let Some(change) = self.undone.pop() {
+            self.done.push(change);
+        }
+    }
+
+    pub fn done(&self) -> &[Change] {
+        &self.done
+    }
+
+    pub fn undone(&self) -> &[Change] {
+        &self.undone
+    }
+}
+
+#[cfg(test)]
+fn alias_change(alias: &str) -> Change {
+    Change::AddAlias {
+        alias: alias.to_owned(),
+        for_builtin: String::from("exit"),
+    }
+}
+
+#[test]
+fn test_undoing_and_redoing_changes() {
+    let mut log = ChangeLog::default();
+    assert_eq!(None, log.to_undo());
+    log.record(alias_change("bye"));
+    log.record(alias_change("quit"));
+    assert_eq!(Some(&alias_change("quit")), log.to_undo());
+
+    log.mark_undone();
+    assert_eq!(Some(&alias_change("bye")), log.to_undo());
+    assert_eq!(Some(&alias_change("quit")), log.to_redo());
+    log.mark_redone();
+    assert_eq!(None, log.to_redo());
+
+    log.mark_undone();
+    log.record(alias_change("leave"));
+    assert_eq!(None, log.to_redo(), "a new change forgets the undone ones");
+    assert_eq!(2, log.done().len());
+}
+
+#[test]
+fn test_inverting_changes() {
+    let change = alias_change("bye");
+    assert_eq!(
+        Change::RemoveAlias {
+            alias: String::from("bye"),
+            for_builtin: String::from("exit")
+        },
+        change.inverse()
+    );
+    assert_eq!(change, change.inverse().inverse());
+    assert_eq!("added alias [bye] for [exit]", change.describe());
+}
@@ -1,31 +1,185 @@
 extern crate chrono;
 extern crate enum_dispatch;
-use chrono::Local;
+use chrono::{Duration, Local};
 use enum_dispatch::*;
 
-use crate::config::get_exit_message;
-use crate::config::get_help_message;
+use crate::config::get_config_file_name;
 use crate::config::get_violet_name;
+use crate::config::CommandHelp;
 use crate::config::Help;
+use crate::data::normalization::Normalization;
+use crate::util::time;
+
+use super::scheduler::JobSchedule;
 
 use serde::{Deserialize, Serialize};
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CommandCategory {
+    Info,
+    Time,
+    Maths,
+    Files,
+    Jobs,
+    Aliases,
+    Session,
+    System,
+}
+
+impl CommandCategory {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CommandCategory::Info => "info",
+            CommandCategory::Time => "time",
+            CommandCategory::Maths => "maths",
+            CommandCategory::Files => "files",
+            CommandCategory::Jobs => "jobs",
+            CommandCategory::Aliases => "aliases",
+            CommandCategory::Session => "session",
+            CommandCategory::System => "system",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppearanceSetting {
+    Prompt,
+    Banner,
+    ExitMessage,
+    Theme,
+}
+
 pub enum InterpretedCommand {
     DoNothing,
-    ListAvailableCommands,
-    Exit { exit_message: String },
-    AddAlias { alias: String, for_builtin: String },
-    RemoveAlias { alias: String },
-    ExplainCommand { command: String },
+    Reply {
+        text: String,
+    },
+    ListAvailableCommands {
+        matching: Option<String>,
+    },
+    ListAliases,
+    Exit,
+    AddAlias {
+        alias: String,
+        for_builtin: String,
+    },
+    RemoveAlias {
+        alias: String,
+    },
+    ExplainCommand {
+        command: String,
+    },
+    ShowHelp {
+        topic: Option<String>,
+    },
+    GenerateDocumentation {
+        directory: String,
+    },
+    SwitchLanguage {
+        language: String,
+    },
+    SetAppearance {
+        setting: AppearanceSetting,
+        value: String,
+    },
+    AddSynonym {
+        synonym: String,
+        for_word: String,
+    },
+    RemoveSynonym {
+        synonym: String,
+    },
+    ListSynonyms,
+    SetShortcut {
+        shortcut: String,
+        for_builtin: String,
+    },
+    RemoveShortcut {
+        shortcut: String,
+    },
+    ListShortcutCollisions,
+    StartStopwatch,
+    StopStopwatch,
+    Remind {
+        after: Duration,
+        text: String,
+    },
+    ScheduleJob {
+        command: String,
+        schedule: JobSchedule,
+    },
+    ListJobs,
+    CancelJob {
+        id: usize,
+    },
+    Calculate {
+        expression: String,
+    },
+    ShowFile {
+        path: String,
+    },
+    ListFiles {
+        folder: String,
+    },
+    CopyFile {
+        from: String,
+        to: String,
+        overwrite: bool,
+    },
+    WhereAmI,
+    GoToFolder {
+        folder: String,
+    },
+    Confirm {
+        question: String,
+        action: Box<InterpretedCommand>,
+    },
+    RemoveConfigFile,
+    SetConfirmations {
+        enabled: bool,
+    },
+    UndoLastChange,
+    RedoLastChange,
+    ShowChangeLog,
+    StartRecording {
+        path: String,
+        overwrite: bool,
+    },
+    StopRecording,
+    ReplaySession {
+        path: String,
+    },
 }
 
 pub enum InterpretationError {
     ArgumentEmpty { argument_name: String },
     ArgSpecifierMisused,
+    ArgumentInvalid { argument: String, reason: String },
+    PathNotFound { path: String },
+    FileOperationFailed { path: String, reason: String },
+}
+
+fn get_argument(
+    args: &[String],
+    index: usize,
+    argument_name: &str,
+) -> Result<String, InterpretationError> {
+    if args[index] == "<ARG>" {
+        return Err(InterpretationError::ArgSpecifierMisused);
+    }
+
+    if args[index].is_empty() {
+        return Err(InterpretationError::ArgumentEmpty {
+            argument_name: argument_name.to_string(),
+        });
+    }
+
+    Ok(args[index].clone())
 }
 
 #[enum_dispatch]
 #[derive(Clone, Serialize, Deserialize, Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum Command {
     ExitCommand,
     CurrentTimeCommand,
@@ -34,59 +188,117 @@ pub enum Command {
     AddAliasCommand,
     RemoveAliasCommand,
     HelpCommand,
+    HelpTopicCommand,
     ListAvailableCommandsCommand,
+    ListAvailableCommandsMatchingCommand,
+    ListAliasesCommand,
     ExplainCommandCommand,
+    AddSynonymCommand,
+    RemoveSynonymCommand,
+    ListSynonymsCommand,
+    SetShortcutCommand,
+    RemoveShortcutCommand,
+    ListShortcutCollisionsCommand,
+    GenerateDocumentationCommand,
+    SwitchLanguageCommand,
+    SetPromptCommand,
+    SetBannerCommand,
+    SetExitMessageCommand,
+    SetThemeCommand,
+    CurrentTimeInCommand,
+    CurrentDateCommand,
+    StartStopwatchCommand,
+    StopStopwatchCommand,
+    RemindMeCommand,
+    TimeUntilCommand,
+    RunInBackgroundCommand,
+    RunEveryCommand,
+    RunAtCommand,
+    ListJobsCommand,
+    CancelJobCommand,
+    CalculateCommand,
+    WhatIsPlusCommand,
+    WhatIsMinusCommand,
+    WhatIsTimesCommand,
+    WhatIsDividedByCommand,
+    ShowFileCommand,
+    ListFilesInCommand,
+    CopyFileCommand,
+    WhereAmICommand,
+    GoToFolderCommand,
+    RemoveConfigFileCommand,
+    SetConfirmationsCommand,
+    UndoLastChangeCommand,
+    RedoLastChangeCommand,
+    ShowChangeLogCommand,
+    StartRecordingCommand,
+    StopRecordingCommand,
+    ReplaySessionCommand,
 }
 
 #[enum_dispatch(Command)]
 pub trait Action {
     fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError>;
-    fn help(&self) -> &str;
+    fn help(&self) -> CommandHelp;
+    fn category(&self) -> CommandCategory;
+    fn normalization(&self) -> Normalization {
+        Normalization::default()
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct ExitCommand;
 impl Action for ExitCommand {
     fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
-        Ok(InterpretedCommand::Exit {
-            exit_message: get_exit_message(),
-        })
+        Ok(InterpretedCommand::Exit)
     }
 
-    fn help(&self) -> &str {
+    fn help(&self) -> CommandHelp {
         Help::exit()
     }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Session
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct CurrentTimeCommand;
 impl Action for CurrentTimeCommand {
     fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
-        println!(
-            "Your system clock says it's {} now!",
-            Local::now().format("%I:%M %p")
-        );
-
-        Ok(InterpretedCommand::DoNothing)
+        Ok(InterpretedCommand::Reply {
+            text: format!(
+                "Your system clock says it's {} now!",
+                Local::now().format("%I:%M %p")
+            ),
+        })
     }
 
-    fn help(&self) -> &str {
+    fn help(&self) -> CommandHelp {
         Help::what_time_is_it()
     }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Time
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct WhatsYourNameCommand;
 impl Action for WhatsYourNameCommand {
     fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
-        println!("My name is {}! Nice to meet you ^_^", &get_violet_name());
-
-        Ok(InterpretedCommand::DoNothing)
+        Ok(InterpretedCommand::Reply {
+            text: format!("My name is {}! Nice to meet you ^_^", &get_violet_name()),
+        })
     }
 
-    fn help(&self) -> &str {
+    fn help(&self) -> CommandHelp {
         Help::what_is_your_name()
     }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Info
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -97,18 +309,22 @@ impl Action for SayThisAndThatCommand {
             return Err(InterpretationError::ArgSpecifierMisused);
         }
 
-        println!(
-            "Gotcha. Saying {} and {}!",
-            args.get(0).unwrap(),
-            args.get(1).unwrap()
-        );
-
-        Ok(InterpretedCommand::DoNothing)
+        Ok(InterpretedCommand::Reply {
+            text: format!(
+                "Gotcha. Saying {} and {}!",
+                args.first().map(String::as_str).unwrap_or_default(),
+                args.get(1).map(String::as_str).unwrap_or_default()
+            ),
+        })
     }
 
-    fn help(&self) -> &str {
+    fn help(&self) -> CommandHelp {
         Help::please_say_arg_and_arg()
     }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Info
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -136,9 +352,17 @@ impl Action for AddAliasCommand {
         })
     }
 
-    fn help(&self) -> &str {
+    fn help(&self) -> CommandHelp {
         Help::add_alias_arg_for_builtin_arg()
     }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Aliases
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -155,40 +379,109 @@ impl Action for RemoveAliasCommand {
             });
         }
 
-        Ok(InterpretedCommand::RemoveAlias {
-            alias: args[0].clone(),
+        Ok(InterpretedCommand::Confirm {
+            question: format!("Remove alias {}?", args[0]),
+            action: Box::new(InterpretedCommand::RemoveAlias {
+                alias: args[0].clone(),
+            }),
         })
     }
 
-    fn help(&self) -> &str {
+    fn help(&self) -> CommandHelp {
         Help::remove_alias_arg()
     }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Aliases
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct HelpCommand;
 impl Action for HelpCommand {
     fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
-        println!("{}", &get_help_message());
-
-        Ok(InterpretedCommand::DoNothing)
+        Ok(InterpretedCommand::ShowHelp { topic: None })
     }
 
-    fn help(&self) -> &str {
+    fn help(&self) -> CommandHelp {
         Help::help()
     }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Info
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct HelpTopicCommand;
+impl Action for HelpTopicCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        let topic = get_argument(&args, 0, "help topic")?;
+
+        Ok(InterpretedCommand::ShowHelp { topic: Some(topic) })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::help_topic_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Info
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct ListAvailableCommandsCommand;
 impl Action for ListAvailableCommandsCommand {
     fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
-        Ok(InterpretedCommand::ListAvailableCommands)
+        Ok(InterpretedCommand::ListAvailableCommands { matching: None })
     }
 
-    fn help(&self) -> &str {
+    fn help(&self) -> CommandHelp {
         Help::list_available_commands()
     }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Info
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ListAvailableCommandsMatchingCommand;
+impl Action for ListAvailableCommandsMatchingCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::ListAvailableCommands {
+            matching: Some(get_argument(&args, 0, "text to match")?),
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::list_available_commands_matching_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Info
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ListAliasesCommand;
+impl Action for ListAliasesCommand {
+    fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::ListAliases)
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::list_aliases()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Aliases
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -210,7 +503,950 @@ impl Action for ExplainCommandCommand {
         })
     }
 
-    fn help(&self) -> &str {
+    fn help(&self) -> CommandHelp {
         Help::explain_command_arg()
     }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Info
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct AddSynonymCommand;
+impl Action for AddSynonymCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::AddSynonym {
+            synonym: get_argument(&args, 0, "synonym to add")?,
+            for_word: get_argument(&args, 1, "command word")?,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::add_synonym_arg_for_word_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::System
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RemoveSynonymCommand;
+impl Action for RemoveSynonymCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::RemoveSynonym {
+            synonym: get_argument(&args, 0, "synonym to remove")?,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::remove_synonym_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::System
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ListSynonymsCommand;
+impl Action for ListSynonymsCommand {
+    fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::ListSynonyms)
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::list_synonyms()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::System
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SetShortcutCommand;
+impl Action for SetShortcutCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::SetShortcut {
+            shortcut: get_argument(&args, 0, "shortcut to set")?,
+            for_builtin: get_argument(&args, 1, "builtin name")?,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::set_shortcut_arg_for_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::System
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RemoveShortcutCommand;
+impl Action for RemoveShortcutCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::RemoveShortcut {
+            shortcut: get_argument(&args, 0, "shortcut to remove")?,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::remove_shortcut_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::System
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ListShortcutCollisionsCommand;
+impl Action for ListShortcutCollisionsCommand {
+    fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::ListShortcutCollisions)
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::list_shortcut_collisions()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::System
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct GenerateDocumentationCommand;
+impl Action for GenerateDocumentationCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::GenerateDocumentation {
+            directory: get_argument(&args, 0, "documentation folder")?,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::generate_documentation_to_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::System
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SwitchLanguageCommand;
+impl Action for SwitchLanguageCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::SwitchLanguage {
+            language: get_argument(&args, 0, "language")?,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::switch_language_to_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::System
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SetPromptCommand;
+impl Action for SetPromptCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::SetAppearance {
+            setting: AppearanceSetting::Prompt,
+            value: get_argument(&args, 0, "prompt")?,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::set_prompt_to_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Session
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SetBannerCommand;
+impl Action for SetBannerCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::SetAppearance {
+            setting: AppearanceSetting::Banner,
+            value: get_argument(&args, 0, "banner")?,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::set_banner_to_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Session
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SetExitMessageCommand;
+impl Action for SetExitMessageCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::SetAppearance {
+            setting: AppearanceSetting::ExitMessage,
+            value: get_argument(&args, 0, "exit message")?,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::set_exit_message_to_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Session
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SetThemeCommand;
+impl Action for SetThemeCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::SetAppearance {
+            setting: AppearanceSetting::Theme,
+            value: get_argument(&args, 0, "theme")?,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::set_theme_to_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Session
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CurrentTimeInCommand;
+impl Action for CurrentTimeInCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        let place = get_argument(&args, 0, "place")?;
+
+        let zone = time::find_time_zone(&place).ok_or_else(|| {
+            InterpretationError::ArgumentInvalid {
+                argument: place,
+                reason: String::from("there's no such time zone, try a city like \"Kyiv\" or a zone like \"Europe/Kyiv\""),
+            }
+        })?;
+        let now = Local::now().with_timezone(&zone);
+
+        Ok(InterpretedCommand::Reply {
+            text: format!(
+                "It's {} in {} ({}, UTC{}).",
+                now.format("%I:%M %p"),
+                zone.name(),
+                now.format("%Z"),
+                now.format("%:z")
+            ),
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::what_time_is_it_in_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Time
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CurrentDateCommand;
+impl Action for CurrentDateCommand {
+    fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::Reply {
+            text: format!("Today is {}.", Local::now().format("%A, %B %-d, %Y")),
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::what_is_the_date()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Time
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct StartStopwatchCommand;
+impl Action for StartStopwatchCommand {
+    fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::StartStopwatch)
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::start_stopwatch()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Time
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct StopStopwatchCommand;
+impl Action for StopStopwatchCommand {
+    fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::StopStopwatch)
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::stop_stopwatch()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Time
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RemindMeCommand;
+impl Action for RemindMeCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        if args.iter().any(|arg| arg == "<ARG>") {
+            return Err(InterpretationError::ArgSpecifierMisused);
+        }
+
+        let after = time::parse_duration(&args[0]).map_err(|reason| {
+            InterpretationError::ArgumentInvalid {
+                argument: args[0].clone(),
+                reason,
+            }
+        })?;
+
+        Ok(InterpretedCommand::Remind {
+            after,
+            text: get_argument(&args, 1, "what to remind you about")?,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::remind_me_in_arg_to_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Time
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TimeUntilCommand;
+impl Action for TimeUntilCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        let until = get_argument(&args, 0, "moment")?;
+
+        let now = Local::now();
+        let moment = time::parse_moment(&until, now).map_err(|reason| {
+            InterpretationError::ArgumentInvalid {
+                argument: until,
+                reason,
+            }
+        })?;
+        let left = moment - now;
+        let when = moment.format("%A, %B %-d, %Y at %I:%M %p");
+        let text = if left < Duration::zero() {
+            format!("{} was {} ago.", when, time::format_duration(left))
+        } else {
+            format!("{} is in {}.", when, time::format_duration(left))
+        };
+
+        Ok(InterpretedCommand::Reply { text })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::how_long_until_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Time
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RunInBackgroundCommand;
+impl Action for RunInBackgroundCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::ScheduleJob {
+            command: get_argument(&args, 0, "command to run")?,
+            schedule: JobSchedule::Now,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::run_arg_in_background()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Jobs
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RunEveryCommand;
+impl Action for RunEveryCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        let every = get_argument(&args, 0, "interval")?;
+        let command = get_argument(&args, 1, "command to run")?;
+        let interval = time::parse_duration(&every)
+            .and_then(|interval| {
+                if interval < Duration::seconds(1) {
+                    Err(String::from(
+                        "jobs can't repeat more often than every second",
+                    ))
+                } else {
+                    Ok(interval)
+                }
+            })
+            .map_err(|reason| InterpretationError::ArgumentInvalid {
+                argument: every,
+                reason,
+            })?;
+
+        Ok(InterpretedCommand::ScheduleJob {
+            command,
+            schedule: JobSchedule::Every(interval),
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::every_arg_run_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Jobs
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RunAtCommand;
+impl Action for RunAtCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        let at = get_argument(&args, 0, "moment")?;
+        let command = get_argument(&args, 1, "command to run")?;
+        let now = Local::now();
+        let moment = time::parse_moment(&at, now)
+            .and_then(|moment| {
+                if moment < now {
+                    Err(String::from("that moment has already passed"))
+                } else {
+                    Ok(moment)
+                }
+            })
+            .map_err(|reason| InterpretationError::ArgumentInvalid {
+                argument: at,
+                reason,
+            })?;
+
+        Ok(InterpretedCommand::ScheduleJob {
+            command,
+            schedule: JobSchedule::At(moment),
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::at_arg_run_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Jobs
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ListJobsCommand;
+impl Action for ListJobsCommand {
+    fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::ListJobs)
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::list_jobs()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Jobs
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CancelJobCommand;
+impl Action for CancelJobCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        let job = get_argument(&args, 0, "job number")?;
+
+        let id = job.trim_start_matches('#').parse::<usize>().map_err(|_| {
+            InterpretationError::ArgumentInvalid {
+                argument: job,
+                reason: String::from(
+                    "job numbers are whole numbers, like the ones \"list jobs\" shows",
+                ),
+            }
+        })?;
+
+        Ok(InterpretedCommand::CancelJob { id })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::cancel_job_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Jobs
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CalculateCommand;
+impl Action for CalculateCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        let expression = get_argument(&args, 0, "expression")?;
+
+        Ok(InterpretedCommand::Calculate {
+            expression: expression.trim_end_matches(['?', '!']).to_owned(),
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::calculate_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Maths
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+fn calculate_operation(
+    args: &[String],
+    operator: &str,
+) -> Result<InterpretedCommand, InterpretationError> {
+    if args.iter().any(|arg| arg == "<ARG>") {
+        return Err(InterpretationError::ArgSpecifierMisused);
+    }
+
+    let mut operands: Vec<String> = vec![];
+    for arg in args {
+        let arg = arg.trim_end_matches(['?', '!']);
+        if arg.is_empty() {
+            return Err(InterpretationError::ArgumentEmpty {
+                argument_name: "number".to_string(),
+            });
+        }
+        if arg.chars().all(|ch| ch.is_alphanumeric() || ch == '.') {
+            operands.push(arg.to_owned());
+        } else {
+            operands.push(format!("({})", arg));
+        }
+    }
+
+    Ok(InterpretedCommand::Calculate {
+        expression: operands.join(&format!(" {} ", operator)),
+    })
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct WhatIsPlusCommand;
+impl Action for WhatIsPlusCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        calculate_operation(&args, "+")
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::what_is_arg_plus_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Maths
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct WhatIsMinusCommand;
+impl Action for WhatIsMinusCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        calculate_operation(&args, "-")
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::what_is_arg_minus_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Maths
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct WhatIsTimesCommand;
+impl Action for WhatIsTimesCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        calculate_operation(&args, "*")
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::what_is_arg_times_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Maths
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct WhatIsDividedByCommand;
+impl Action for WhatIsDividedByCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        calculate_operation(&args, "/")
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::what_is_arg_divided_by_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Maths
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ShowFileCommand;
+impl Action for ShowFileCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::ShowFile {
+            path: get_argument(&args, 0, "file")?,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::show_file_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Files
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ListFilesInCommand;
+impl Action for ListFilesInCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::ListFiles {
+            folder: get_argument(&args, 0, "folder")?,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::list_files_in_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Files
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CopyFileCommand;
+impl Action for CopyFileCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::CopyFile {
+            from: get_argument(&args, 0, "file to copy")?,
+            to: get_argument(&args, 1, "destination")?,
+            overwrite: false,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::copy_file_arg_to_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Files
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct WhereAmICommand;
+impl Action for WhereAmICommand {
+    fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::WhereAmI)
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::where_am_i()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Files
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct GoToFolderCommand;
+impl Action for GoToFolderCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::GoToFolder {
+            folder: get_argument(&args, 0, "folder")?,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::go_to_folder_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Files
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RemoveConfigFileCommand;
+impl Action for RemoveConfigFileCommand {
+    fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::Confirm {
+            question: format!("Delete the config file {}?", get_config_file_name()),
+            action: Box::new(InterpretedCommand::RemoveConfigFile),
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::remove_config_file()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::System
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SetConfirmationsCommand;
+impl Action for SetConfirmationsCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        let answer = get_argument(&args, 0, "on or off")?;
+
+        let enabled = match answer.to_lowercase().as_str() {
+            "on" | "ask" | "yes" | "default" => true,
+            "off" | "skip" | "no" => false,
+            _ => {
+                return Err(InterpretationError::ArgumentInvalid {
+                    argument: answer,
+                    reason: String::from(
+                        "use \"on\" to be asked before destructive commands or \"off\" to skip the questions",
+                    ),
+                })
+            }
+        };
+
+        Ok(InterpretedCommand::SetConfirmations { enabled })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::set_confirmations_to_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Session
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct UndoLastChangeCommand;
+impl Action for UndoLastChangeCommand {
+    fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::UndoLastChange)
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::undo_last_change()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Aliases
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RedoLastChangeCommand;
+impl Action for RedoLastChangeCommand {
+    fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::RedoLastChange)
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::redo_last_change()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Aliases
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ShowChangeLogCommand;
+impl Action for ShowChangeLogCommand {
+    fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::ShowChangeLog)
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::show_change_log()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Aliases
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct StartRecordingCommand;
+impl Action for StartRecordingCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::StartRecording {
+            path: get_argument(&args, 0, "transcript file")?,
+            overwrite: false,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::start_recording_to_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Session
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct StopRecordingCommand;
+impl Action for StopRecordingCommand {
+    fn execute(&self, _args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::StopRecording)
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::stop_recording()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Session
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ReplaySessionCommand;
+impl Action for ReplaySessionCommand {
+    fn execute(&self, args: Vec<String>) -> Result<InterpretedCommand, InterpretationError> {
+        Ok(InterpretedCommand::ReplaySession {
+            path: get_argument(&args, 0, "transcript file")?,
+        })
+    }
+
+    fn help(&self) -> CommandHelp {
+        Help::replay_session_arg()
+    }
+
+    fn category(&self) -> CommandCategory {
+        CommandCategory::Session
+    }
+
+    fn normalization(&self) -> Normalization {
+        Normalization::without_fillers()
+    }
 }
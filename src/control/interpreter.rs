@@ -1,283 +1,3070 @@
 use crate::config;
+use crate::data::locale::{Locale, Message};
+use crate::data::normalization::Normalization;
 use crate::data::pathtree::*;
+use crate::data::synonyms::Synonyms;
+use crate::data::userconfig::UserConfig;
+use crate::io::appearance::{self, Theme};
+use crate::io::docs::{self, CommandDoc};
 use crate::io::input;
+use crate::io::output::{CommandRecord, Output, OutputMode, Severity};
+use crate::io::transcript::{self, Recorder};
+use crate::util::calc::{self, Number};
 use crate::util::string::clone_uppercased;
+use crate::util::time;
 use crate::util::treepath::TreePath;
 
-use std::path::Path;
-use std::process::exit;
+use chrono::{Duration, Local};
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
 
+use super::changes::{Change, ChangeLog};
 use super::commands::*;
+use super::scheduler::{Job, JobSchedule, Scheduler};
+
+#[cfg(test)]
+mod golden;
 
 pub struct Interpreter {
     builtin_commands: PathTree<Command>,
-    aliases_for_builtins: PathTree<String>,
-    aliases_len_on_boot: usize,
+    builtin_shortcuts: BTreeMap<String, String>,
+    user_config: UserConfig,
+    config_empty_on_boot: bool,
+    config_load_failed: bool,
+    synonyms_changed: bool,
+    locale: Locale,
+    localized_paths: BTreeMap<String, String>,
+    last_status: i32,
+    exiting: bool,
+    stopwatch: Option<Instant>,
+    scheduler: Arc<Scheduler>,
+    last_result: Option<Number>,
+    cwd: PathBuf,
+    pending_confirmation: Option<InterpretedCommand>,
+    confirmations: bool,
+    changes: ChangeLog,
+    recorder: Option<Recorder>,
+    state_dir: PathBuf,
+    output: Output,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Interpreter {
     pub fn new() -> Self {
+        Self::with_output(Output::new(OutputMode::Text, appearance::colours_enabled()))
+    }
+
+    pub fn with_output(mut output: Output) -> Self {
         let mut builtins: PathTree<Command> = PathTree::new();
         let config_name = config::get_config_file_name();
-        let aliases: PathTree<String> = if Path::new(&config_name).is_file() {
+        let mut config_load_failed = false;
+        let mut user_config: UserConfig = if Path::new(&config_name).is_file() {
             match std::fs::read_to_string(&config_name) {
-                Ok(file_contents) => match serde_json::from_str(&file_contents) {
-                    Ok(the_aliases) => {
-                        println!("INFO: loaded the saved aliases from config file successfully!");
-                        the_aliases
+                Ok(file_contents) => match UserConfig::from_json(&file_contents) {
+                    Ok(the_config) => {
+                        output.info(String::from(
+                            "loaded the saved aliases and shortcuts from config file successfully!",
+                        ));
+                        the_config
                     }
                     Err(the_err) => {
-                        println!("ERROR: the config file is corrupted, couldn't get aliases from it: {:?}", the_err);
-                        PathTree::new()
+                        output.error(format!("the config file is corrupted, couldn't get aliases and shortcuts from it: {:?}", the_err));
+                        config_load_failed = true;
+                        UserConfig::default()
                     }
                 },
                 Err(the_err) => {
-                    println!(
-                        "ERROR: couldn't open config file to load the saved aliases from it: {:?}",
+                    output.error(format!(
+                        "couldn't open config file to load the saved aliases and shortcuts from it: {:?}",
                         the_err
-                    );
-                    PathTree::new()
+                    ));
+                    config_load_failed = true;
+                    UserConfig::default()
                 }
             }
         } else {
-            PathTree::new()
+            UserConfig::default()
         };
-        let alias_len = aliases.tree.len();
+        let config_empty_on_boot = user_config.is_empty();
+        user_config
+            .aliases
+            .set_default_normalization(Normalization::default());
 
-        Interpreter::set_all_builtins(&mut builtins);
-        Self {
+        let locale = user_config.language.unwrap_or_else(Locale::from_env);
+
+        let builtin_shortcuts = Interpreter::set_all_builtins(&mut builtins, &mut output);
+        builtins.set_synonyms(Interpreter::load_synonyms(&mut output));
+        let mut interpreter = Self {
             builtin_commands: builtins,
-            aliases_for_builtins: aliases,
-            aliases_len_on_boot: alias_len,
+            builtin_shortcuts,
+            user_config,
+            config_empty_on_boot,
+            config_load_failed,
+            synonyms_changed: false,
+            locale: Locale::default(),
+            localized_paths: BTreeMap::new(),
+            last_status: 0,
+            exiting: false,
+            stopwatch: None,
+            scheduler: Scheduler::new(),
+            last_result: None,
+            cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            pending_confirmation: None,
+            confirmations: true,
+            changes: ChangeLog::default(),
+            recorder: None,
+            state_dir: PathBuf::new(),
+            output,
+        };
+        interpreter.apply_vocabulary(locale);
+        interpreter.apply_user_shortcuts();
+
+        interpreter
+    }
+
+    fn apply_vocabulary(&mut self, locale: Locale) {
+        for localized in std::mem::take(&mut self.localized_paths).keys() {
+            if let Err(the_err) = self.builtin_commands.drop_by_path(localized) {
+                self.output.error(format!(
+                    "couldn't forget the command [{}]: {}",
+                    localized, the_err
+                ));
+            }
+        }
+
+        for (path, localized) in locale.vocabulary() {
+            let builtin = match self.builtin_commands.get_value_by_path(path) {
+                Some(builtin) => builtin.clone(),
+                None => continue,
+            };
+            if self.builtin_commands.is_node_active(localized) {
+                self.output.error(format!(
+                    "can't use [{}] for [{}], it's already taken by another command.",
+                    localized, path
+                ));
+                continue;
+            }
+
+            let normalization = builtin.normalization();
+            let registered = self
+                .builtin_commands
+                .set_by_path(builtin, localized)
+                .and_then(|_| {
+                    if normalization != Normalization::default() {
+                        self.builtin_commands
+                            .set_normalization(localized, normalization)?;
+                    }
+                    Ok(())
+                });
+            match registered {
+                Ok(()) => {
+                    self.localized_paths
+                        .insert(localized.to_string(), path.to_string());
+                }
+                Err(the_err) => self.output.error(format!(
+                    "couldn't register the command [{}] for [{}]: {}",
+                    localized, path, the_err
+                )),
+            }
         }
+        self.locale = locale;
     }
 
-    fn set_all_builtins(builtins: &mut PathTree<Command>) {
-        builtins.set_by_path_with_shortcut(Command::from(ExitCommand), "exit");
-        builtins.set_by_path_with_shortcut(Command::from(CurrentTimeCommand), "what time is it");
-        builtins
-            .set_by_path_with_shortcut(Command::from(WhatsYourNameCommand), "what is your name");
-        builtins.set_by_path_with_shortcut(
-            Command::from(SayThisAndThatCommand),
-            "please say <ARG> and <ARG>",
-        );
-        builtins.set_by_path_with_shortcut(
-            Command::from(AddAliasCommand),
-            "add alias <ARG> for builtin <ARG>",
-        );
-        builtins.set_by_path_with_shortcut(Command::from(RemoveAliasCommand), "remove alias <ARG>");
-        builtins.set_by_path_with_shortcut(Command::from(HelpCommand), "help");
-        builtins.set_by_path_with_shortcut(
-            Command::from(ListAvailableCommandsCommand),
-            "list available commands",
-        );
-        builtins.set_by_path_with_shortcut(
-            Command::from(ExplainCommandCommand),
-            "explain command <ARG>",
-        );
+    fn canonical_path(&self, path: &str) -> String {
+        match self.localized_paths.get(path) {
+            Some(english) => english.clone(),
+            None => path.to_owned(),
+        }
+    }
+
+    fn is_listed_builtin(&self, path: &str) -> bool {
+        !TreePath::is_path_a_shortcut(path) && !self.localized_paths.contains_key(path)
+    }
+
+    fn localized_name(&self, path: &str) -> Option<&String> {
+        self.localized_paths
+            .iter()
+            .find(|(_, english)| english.as_str() == path)
+            .map(|(localized, _)| localized)
+    }
+
+    fn switch_language(&mut self, language: String) {
+        let locale = match Locale::from_name(&language) {
+            Some(locale) => locale,
+            None => {
+                let languages: Vec<String> = Locale::all()
+                    .iter()
+                    .map(|locale| format!("{} ({})", locale.code(), locale.name()))
+                    .collect();
+                let message = self.locale.format(
+                    Message::LanguageUnknown,
+                    &[&language, &languages.join(", ")],
+                );
+                self.report_error(message);
+                return;
+            }
+        };
+
+        if locale != self.locale {
+            self.apply_vocabulary(locale);
+        }
+        self.user_config.language = Some(locale);
+        let message = self
+            .locale
+            .format(Message::LanguageSwitched, &[locale.name()]);
+        self.output.info(message);
+    }
+
+    fn apply_user_shortcuts(&mut self) {
+        let user_shortcuts = std::mem::take(&mut self.user_config.shortcuts);
+        for (shortcut, for_builtin) in user_shortcuts {
+            let shortcut_name = TreePath::get_shortcut_name(&shortcut).unwrap_or_default();
+            if let Err(the_err) = self.register_user_shortcut(&shortcut_name, &for_builtin) {
+                self.output.error(format!(
+                    "couldn't restore the saved shortcut {} for [{}]: {}",
+                    shortcut, for_builtin, the_err
+                ));
+            }
+        }
     }
 
-    fn exit(&mut self, exit_message: String) {
-        if !self.aliases_for_builtins.tree.is_empty() {
-            match std::fs::File::create(config::get_config_file_name()) {
-                Ok(file) => match serde_json::to_writer_pretty(file, &self.aliases_for_builtins) {
-                    Ok(()) => println!("INFO: saved aliases successfully before exiting ^_^"),
-                    Err(the_err) => println!(
-                        "ERROR: opened file, but weren't able to save aliases to it: {:?}",
+    fn load_synonyms(output: &mut Output) -> Synonyms {
+        let synonyms_name = config::get_synonyms_file_name();
+        if !Path::new(&synonyms_name).is_file() {
+            return Synonyms::with_defaults();
+        }
+
+        match std::fs::read_to_string(&synonyms_name) {
+            Ok(file_contents) => match serde_json::from_str(&file_contents) {
+                Ok(the_synonyms) => {
+                    output.info(String::from("loaded the synonym dictionary successfully!"));
+                    the_synonyms
+                }
+                Err(the_err) => {
+                    output.error(format!(
+                        "the synonym dictionary is corrupted, using the default synonyms instead: {:?}",
                         the_err
-                    ),
+                    ));
+                    Synonyms::with_defaults()
+                }
+            },
+            Err(the_err) => {
+                output.error(format!(
+                    "couldn't open the synonym dictionary, using the default synonyms instead: {:?}",
+                    the_err
+                ));
+                Synonyms::with_defaults()
+            }
+        }
+    }
+
+    fn set_all_builtins(
+        builtins: &mut PathTree<Command>,
+        output: &mut Output,
+    ) -> BTreeMap<String, String> {
+        let all_builtins: Vec<(Command, &str, &str)> = vec![
+            (Command::from(ExitCommand), "exit", "[e]"),
+            (
+                Command::from(CurrentTimeCommand),
+                "what time is it",
+                "[wtii]",
+            ),
+            (
+                Command::from(CurrentTimeInCommand),
+                "what time is it in <ARG>",
+                "[wtiia]",
+            ),
+            (
+                Command::from(CurrentDateCommand),
+                "what is the date",
+                "[witd]",
+            ),
+            (
+                Command::from(StartStopwatchCommand),
+                "start stopwatch",
+                "[ss]",
+            ),
+            (
+                Command::from(StopStopwatchCommand),
+                "stop stopwatch",
+                "[sts]",
+            ),
+            (
+                Command::from(RemindMeCommand),
+                "remind me in <ARG> to <ARG>",
+                "[rmiata]",
+            ),
+            (
+                Command::from(TimeUntilCommand),
+                "how long until <ARG>",
+                "[hlua]",
+            ),
+            (
+                Command::from(WhatsYourNameCommand),
+                "what is your name",
+                "[wiyn]",
+            ),
+            (
+                Command::from(SayThisAndThatCommand),
+                "please say <ARG> and <ARG>",
+                "[psaaa]",
+            ),
+            (
+                Command::from(AddAliasCommand),
+                "add alias <ARG> for builtin <ARG>",
+                "[aaafba]",
+            ),
+            (
+                Command::from(RemoveAliasCommand),
+                "remove alias <ARG>",
+                "[raa]",
+            ),
+            (Command::from(HelpCommand), "help", "[h]"),
+            (Command::from(HelpTopicCommand), "help topic <ARG>", "[hta]"),
+            (
+                Command::from(ListAvailableCommandsCommand),
+                "list available commands",
+                "[lac]",
+            ),
+            (
+                Command::from(ListAvailableCommandsMatchingCommand),
+                "list available commands matching <ARG>",
+                "[lacma]",
+            ),
+            (Command::from(ListAliasesCommand), "list aliases", "[la]"),
+            (
+                Command::from(ExplainCommandCommand),
+                "explain command <ARG>",
+                "[eca]",
+            ),
+            (
+                Command::from(AddSynonymCommand),
+                "add synonym <ARG> for word <ARG>",
+                "[asafwa]",
+            ),
+            (
+                Command::from(RemoveSynonymCommand),
+                "remove synonym <ARG>",
+                "[rsa]",
+            ),
+            (Command::from(ListSynonymsCommand), "list synonyms", "[ls]"),
+            (
+                Command::from(SetShortcutCommand),
+                "set shortcut <ARG> for <ARG>",
+                "[ssafa]",
+            ),
+            (
+                Command::from(RemoveShortcutCommand),
+                "remove shortcut <ARG>",
+                "[rsha]",
+            ),
+            (
+                Command::from(ListShortcutCollisionsCommand),
+                "list shortcut collisions",
+                "[lsc]",
+            ),
+            (
+                Command::from(GenerateDocumentationCommand),
+                "generate documentation to <ARG>",
+                "[gdta]",
+            ),
+            (
+                Command::from(SwitchLanguageCommand),
+                "switch language to <ARG>",
+                "[slta]",
+            ),
+            (
+                Command::from(SetPromptCommand),
+                "set prompt to <ARG>",
+                "[spta]",
+            ),
+            (
+                Command::from(SetBannerCommand),
+                "set banner to <ARG>",
+                "[sbta]",
+            ),
+            (
+                Command::from(RunInBackgroundCommand),
+                "run <ARG> in background",
+                "[raib]",
+            ),
+            (
+                Command::from(RunEveryCommand),
+                "every <ARG> run <ARG>",
+                "[eara]",
+            ),
+            (Command::from(RunAtCommand), "at <ARG> run <ARG>", "[aara]"),
+            (Command::from(CalculateCommand), "calculate <ARG>", "[ca]"),
+            (
+                Command::from(WhatIsPlusCommand),
+                "what is <ARG> plus <ARG>",
+                "[wiapa]",
+            ),
+            (
+                Command::from(WhatIsMinusCommand),
+                "what is <ARG> minus <ARG>",
+                "[wiama]",
+            ),
+            (
+                Command::from(WhatIsTimesCommand),
+                "what is <ARG> times <ARG>",
+                "[wiata]",
+            ),
+            (
+                Command::from(WhatIsDividedByCommand),
+                "what is <ARG> divided by <ARG>",
+                "[wiadba]",
+            ),
+            (Command::from(ShowFileCommand), "show file <ARG>", "[sfa]"),
+            (
+                Command::from(ListFilesInCommand),
+                "list files in <ARG>",
+                "[lfia]",
+            ),
+            (
+                Command::from(CopyFileCommand),
+                "copy file <ARG> to <ARG>",
+                "[cfata]",
+            ),
+            (Command::from(WhereAmICommand), "where am i", "[wai]"),
+            (
+                Command::from(GoToFolderCommand),
+                "go to folder <ARG>",
+                "[gtfa]",
+            ),
+            (
+                Command::from(RemoveConfigFileCommand),
+                "remove config file",
+                "[rcf]",
+            ),
+            (
+                Command::from(SetConfirmationsCommand),
+                "set confirmations to <ARG>",
+                "[scta]",
+            ),
+            (
+                Command::from(UndoLastChangeCommand),
+                "undo last change",
+                "[ulc]",
+            ),
+            (
+                Command::from(RedoLastChangeCommand),
+                "redo last change",
+                "[rlc]",
+            ),
+            (
+                Command::from(ShowChangeLogCommand),
+                "show change log",
+                "[scl]",
+            ),
+            (
+                Command::from(StartRecordingCommand),
+                "start recording to <ARG>",
+                "[srta]",
+            ),
+            (
+                Command::from(StopRecordingCommand),
+                "stop recording",
+                "[sr]",
+            ),
+            (
+                Command::from(ReplaySessionCommand),
+                "replay session <ARG>",
+                "[rpsa]",
+            ),
+            (Command::from(ListJobsCommand), "list jobs", "[lj]"),
+            (Command::from(CancelJobCommand), "cancel job <ARG>", "[cja]"),
+            (
+                Command::from(SetExitMessageCommand),
+                "set exit message to <ARG>",
+                "[semta]",
+            ),
+            (
+                Command::from(SetThemeCommand),
+                "set theme to <ARG>",
+                "[stta]",
+            ),
+        ];
+
+        Interpreter::register_builtins(builtins, all_builtins, output)
+    }
+
+    fn register_builtins(
+        builtins: &mut PathTree<Command>,
+        all_builtins: Vec<(Command, &str, &str)>,
+        output: &mut Output,
+    ) -> BTreeMap<String, String> {
+        let mut builtin_shortcuts: BTreeMap<String, String> = BTreeMap::new();
+        builtins.set_default_normalization(Normalization::default());
+        for (command, path, shortcut) in all_builtins {
+            let normalization = command.normalization();
+            let shortcut =
+                match builtins.set_by_path_with_shortcut(command.clone(), path, Some(shortcut)) {
+                    Ok(PathTreeOk::ShortcutSetOk { shortcut }) => Some(shortcut),
+                    Ok(_) => continue,
+                    Err(PathTreeErr::ShortcutTaken { shortcut }) => {
+                        output.warning(format!(
+                            "the shortcut {} is already taken, so builtin [{}] has no shortcut.",
+                            shortcut, path
+                        ));
+                        match builtins.set_by_path(command, path) {
+                            Ok(_) => None,
+                            Err(the_err) => {
+                                output.warning(format!(
+                                    "couldn't register builtin [{}]: {}",
+                                    path, the_err
+                                ));
+                                continue;
+                            }
+                        }
+                    }
+                    Err(the_err) => {
+                        output
+                            .warning(format!("couldn't register builtin [{}]: {}", path, the_err));
+                        continue;
+                    }
+                };
+
+            if normalization != Normalization::default() {
+                for registered_path in std::iter::once(path).chain(shortcut.as_deref()) {
+                    if let Err(the_err) =
+                        builtins.set_normalization(registered_path, normalization.clone())
+                    {
+                        output.warning(format!(
+                            "couldn't configure matching for builtin [{}]: {}",
+                            registered_path, the_err
+                        ));
+                    }
+                }
+            }
+            if let Some(shortcut) = shortcut {
+                builtin_shortcuts.insert(shortcut, path.to_owned());
+            }
+        }
+
+        builtin_shortcuts
+    }
+
+    fn config_file(&self) -> PathBuf {
+        self.state_dir.join(config::get_config_file_name())
+    }
+
+    fn save_state(&mut self) {
+        let config_name = self.config_file();
+        let config_emptied =
+            self.user_config.is_empty() && !self.config_empty_on_boot && config_name.is_file();
+        if self.config_load_failed {
+            if !self.user_config.is_empty() {
+                self.report_error(format!(
+                    "didn't save your aliases, shortcuts and settings, because {} couldn't be loaded. Fix or remove it so it isn't overwritten.",
+                    config_name.display()
+                ));
+            }
+        } else if !self.user_config.is_empty() || config_emptied {
+            match std::fs::File::create(&config_name) {
+                Ok(file) => match serde_json::to_writer_pretty(file, &self.user_config) {
+                    Ok(()) => self.output.info(String::from(
+                        "saved your aliases, shortcuts and settings successfully before exiting ^_^",
+                    )),
+                    Err(the_err) => self.report_error(format!(
+                        "opened file, but weren't able to save aliases and shortcuts to it: {:?}",
+                        the_err
+                    )),
                 },
-                Err(the_err) => println!(
-                    "ERROR: coudln't create a file to save aliases: {:?}",
+                Err(the_err) => self.report_error(format!(
+                    "coudln't create a file to save aliases and shortcuts: {:?}",
                     the_err
-                ),
+                )),
             }
         }
 
-        let config_name = config::get_config_file_name();
-        if self.aliases_for_builtins.tree.is_empty()
-            && self.aliases_len_on_boot != 0
-            && std::path::Path::new(&config_name).is_file()
-        {
-            println!("INFO: all aliases have been removed, removing the config file...");
-            std::fs::remove_file(&config_name).unwrap();
+        if self.synonyms_changed {
+            let saved = std::fs::File::create(
+                self.state_dir.join(config::get_synonyms_file_name()),
+            )
+            .map(|file| serde_json::to_writer_pretty(file, self.builtin_commands.synonyms()));
+            match saved {
+                Ok(Ok(())) => self.output.info(String::from(
+                    "saved the synonym dictionary successfully ^_^",
+                )),
+                Ok(Err(the_err)) => self.report_error(format!(
+                    "opened file, but weren't able to save synonyms to it: {:?}",
+                    the_err
+                )),
+                Err(the_err) => self.report_error(format!(
+                    "couldn't create a file to save synonyms: {:?}",
+                    the_err
+                )),
+            }
+        }
+
+        if config_emptied {
+            self.output.info(String::from(
+                "all aliases, shortcuts and settings have been removed, so the config file is empty now. Run \"remove config file\" to delete it.",
+            ));
+        }
+    }
+
+    fn exit(&mut self) {
+        self.drop_unanswered_confirmation();
+        self.abandon_jobs();
+        self.save_state();
+        let exit_message = match &self.user_config.appearance.exit_message {
+            Some(template) => self.render_template(template),
+            None => self.locale.text(Message::Goodbye).to_owned(),
+        };
+        let exit_message = appearance::paint(
+            &exit_message,
+            self.user_config.appearance.theme.banner_colour(),
+            self.output.colours_enabled(),
+        );
+        self.output.print(exit_message);
+        self.exiting = true;
+    }
+
+    fn report_error(&mut self, message: String) {
+        self.output.error(message);
+        self.last_status = 1;
+    }
+
+    fn template_variables(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("time", Local::now().format("%H:%M").to_string()),
+            ("status", self.last_status.to_string()),
+            ("aliases", self.user_config.aliases.len().to_string()),
+            ("cwd", self.cwd.display().to_string()),
+            (
+                "result",
+                self.last_result
+                    .map(|result| result.to_string())
+                    .unwrap_or_default(),
+            ),
+            ("name", clone_uppercased(&config::get_violet_name())),
+            ("version", config::get_violet_version()),
+            ("author", config::get_violet_author()),
+        ]
+    }
+
+    fn render_template(&self, template: &str) -> String {
+        appearance::render_template(template, &self.template_variables())
+    }
+
+    fn prompt_text(&self) -> String {
+        match &self.user_config.appearance.prompt {
+            Some(template) => self.render_template(template),
+            None => config::get_prompt_template(),
+        }
+    }
+
+    fn prompt(&self) -> String {
+        format!(
+            "{} ",
+            appearance::paint(
+                &self.prompt_text(),
+                self.user_config.appearance.theme.prompt_colour(),
+                self.output.colours_enabled()
+            )
+        )
+    }
+
+    fn continuation_prompt(&self) -> String {
+        let continuation = format!(
+            "{:>width$}",
+            config::get_continuation_prompt(),
+            width = self.prompt_text().chars().count()
+        );
+        format!(
+            "{} ",
+            appearance::paint(
+                &continuation,
+                self.user_config.appearance.theme.prompt_colour(),
+                self.output.colours_enabled()
+            )
+        )
+    }
+
+    fn banner(&self) -> String {
+        match &self.user_config.appearance.banner {
+            Some(template) => self.render_template(template),
+            None => {
+                let name = clone_uppercased(&config::get_violet_name());
+                [
+                    self.locale.format(Message::Welcome, &[&name]),
+                    self.locale
+                        .format(Message::Version, &[&name, &config::get_violet_version()]),
+                    self.locale
+                        .format(Message::CreatedBy, &[&config::get_violet_author()]),
+                    self.locale.text(Message::HelpHint).to_owned(),
+                ]
+                .join("\n")
+            }
+        }
+    }
+
+    fn set_appearance(&mut self, setting: AppearanceSetting, value: String) {
+        let value = value.trim().to_owned();
+        let reset = value.eq_ignore_ascii_case("default");
+        let appearance = &mut self.user_config.appearance;
+        match setting {
+            AppearanceSetting::Prompt => appearance.prompt = Some(value).filter(|_| !reset),
+            AppearanceSetting::Banner => appearance.banner = Some(value).filter(|_| !reset),
+            AppearanceSetting::ExitMessage => {
+                appearance.exit_message = Some(value).filter(|_| !reset)
+            }
+            AppearanceSetting::Theme => match Theme::from_name(&value) {
+                Some(theme) => appearance.theme = theme,
+                None if reset => appearance.theme = Theme::default(),
+                None => {
+                    let themes: Vec<&str> = Theme::all().iter().map(|theme| theme.name()).collect();
+                    self.report_error(format!(
+                        "there's no theme called \"{}\". The themes are: {}.",
+                        value,
+                        themes.join(", ")
+                    ));
+                    return;
+                }
+            },
         }
 
-        println!("{}", exit_message);
-        exit(0);
+        if setting == AppearanceSetting::Banner {
+            let banner = self.banner();
+            self.output.print(banner);
+        }
+    }
+
+    fn shortcuts_for(&self, builtin: &str) -> Vec<String> {
+        self.builtin_shortcuts
+            .iter()
+            .chain(self.user_config.shortcuts.iter())
+            .filter(|(_, for_builtin)| for_builtin.as_str() == builtin)
+            .map(|(shortcut, _)| shortcut.clone())
+            .collect()
     }
 
-    fn list_available_commands(&mut self) {
-        if !self.builtin_commands.tree.is_empty() {
-            println!("Available commands:\n");
-            for key in self.builtin_commands.tree.keys() {
-                if self.builtin_commands.is_node_active(key) && !TreePath::is_path_a_shortcut(&key)
+    fn list_available_commands(&mut self, matching: Option<String>) {
+        let matching = matching.map(|text| text.to_lowercase());
+        let mut categories: BTreeMap<CommandCategory, Vec<(String, Vec<String>)>> = BTreeMap::new();
+        for (path, builtin) in self.builtin_commands.active_entries() {
+            if !self.is_listed_builtin(&path) {
+                continue;
+            }
+            let shortcuts = self.shortcuts_for(&path);
+            let localized = self.localized_name(&path).cloned();
+            if let Some(text) = &matching {
+                if !path.to_lowercase().contains(text.as_str())
+                    && !localized
+                        .iter()
+                        .any(|localized| localized.to_lowercase().contains(text.as_str()))
+                    && !shortcuts
+                        .iter()
+                        .any(|shortcut| shortcut.to_lowercase().contains(text.as_str()))
                 {
-                    println!("- {};", key);
+                    continue;
+                }
+            }
+            categories
+                .entry(builtin.category())
+                .or_default()
+                .push((path, shortcuts));
+        }
+
+        if categories.is_empty() {
+            match matching {
+                Some(text) => self
+                    .output
+                    .print(format!("No commands matching \"{}\"!", text)),
+                None => self.output.print(String::from("No commands available!")),
+            }
+            return;
+        }
+
+        let mut listing = vec![String::from("Available commands:")];
+        for (category, commands) in categories {
+            listing.push(format!("\n[{}]", category.name()));
+            for (path, shortcuts) in commands {
+                let arg_count = TreePath::count_x_nodes_for_path(&path, "<ARG>");
+                let highlighted = match self.localized_name(&path) {
+                    Some(localized) => format!(
+                        "{} | {}",
+                        self.output.highlight_path(&path),
+                        self.output.highlight_path(localized)
+                    ),
+                    None => self.output.highlight_path(&path),
+                };
+                let shortcuts = shortcuts
+                    .iter()
+                    .filter_map(|shortcut| TreePath::get_shortcut_name(shortcut))
+                    .map(|shortcut| self.output.highlight(&shortcut))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                listing.push(format!(
+                    "- {} ({}, {} arg{});",
+                    highlighted,
+                    shortcuts,
+                    arg_count,
+                    if arg_count == 1 { "" } else { "s" }
+                ));
+            }
+        }
+        self.output.print(listing.join("\n"));
+        self.output.print(String::from("\nTo explain an individual command, please run:\n<<VIO>> explain command <ARG>\n, where <ARG> is the command you want explained.\nIf the command consists of several words/nodes, take care to enclose it in quotation marks \" when passing it as an argument to explain command."));
+    }
+
+    fn list_aliases(&mut self) {
+        if self.user_config.aliases.is_empty() {
+            self.output.print(String::from(
+                "No aliases yet! You can add one with \"add alias <ARG> for builtin <ARG>\".",
+            ));
+            return;
+        }
+
+        let mut listing = vec![String::from("Your aliases:\n")];
+        for (alias, for_builtin) in self.user_config.aliases.active_entries() {
+            listing.push(format!(
+                "- {} => {};",
+                self.output.highlight_path(&alias),
+                self.output.highlight_path(for_builtin)
+            ));
+        }
+        self.output.print(listing.join("\n"));
+    }
+
+    fn resolve_for_explanation(&self, command: &str) -> Result<(String, Vec<String>), String> {
+        let mut resolution: Vec<String> = vec![];
+        let mut to_explain = command.to_owned();
+        if !self.builtin_commands.is_node_active(command) {
+            if let Ok(PathMatch { path, args, .. }) = self.user_config.aliases.resolve(command) {
+                if let Some(for_builtin) = self.user_config.aliases.get_value_by_path(&path) {
+                    resolution.push(format!("[{}] is an alias for [{}].", path, for_builtin));
+                    to_explain = match TreePath::reconstruct_argumented_path(for_builtin, args) {
+                        Ok(reconstructed) => reconstructed,
+                        Err(_) => for_builtin.clone(),
+                    };
                 }
             }
-            println!("\nTo explain an individual command, please run:\n<<VIO>> explain command <ARG>\n, where <ARG> is the command you want explained.\nIf the command consists of several words/nodes, take care to enclose it in quotation marks \" when passing it as an argument to explain command.");
+        }
+
+        let resolved = if self.builtin_commands.is_node_active(&to_explain) {
+            Ok(PathMatch {
+                path: to_explain.clone(),
+                args: vec![],
+                specificity: 0,
+            })
         } else {
-            println!("No commands available!");
+            self.builtin_commands.resolve(&to_explain)
+        };
+        let PathMatch { path, args, .. } = match resolved {
+            Ok(found) => found,
+            Err(PathTreeErr::AmbiguousMatch { candidates, .. }) => {
+                return Err(format!(
+                    "can't explain command \"{}\", it could mean any of: [{}]. Please quote the arguments to disambiguate",
+                    command,
+                    candidates.join("], [")
+                ));
+            }
+            Err(_) => {
+                return Err(format!(
+                    "can't explain command \"{}\" which doesn't exist",
+                    command
+                ));
+            }
+        };
+
+        let builtin_path = match self
+            .builtin_shortcuts
+            .get(&path)
+            .or_else(|| self.user_config.shortcuts.get(&path))
+        {
+            Some(for_builtin) => {
+                resolution.push(format!("{} is a shortcut for [{}].", path, for_builtin));
+                for_builtin.clone()
+            }
+            None => match self.localized_paths.get(&path) {
+                Some(english) => {
+                    resolution.push(format!(
+                        "[{}] means [{}] ({}).",
+                        path,
+                        english,
+                        self.locale.name()
+                    ));
+                    english.clone()
+                }
+                None => path,
+            },
+        };
+        if !args.is_empty() {
+            resolution.push(format!(
+                "\"{}\" runs [{}] with the arguments: \"{}\".",
+                command,
+                builtin_path,
+                args.join("\", \"")
+            ));
         }
+
+        Ok((builtin_path, resolution))
     }
 
     fn explain_command(&mut self, command: &str) {
-        if !self.builtin_commands.is_node_active(command) {
-            println!(
-                "ERROR: can't explain command \"{}\" which doesn't exist.",
-                command
-            );
+        let command = TreePath::prettify(command);
+        let (builtin_path, resolution) = match self.resolve_for_explanation(&command) {
+            Ok(resolved) => resolved,
+            Err(the_err) => {
+                self.report_error(format!("{}.", the_err));
+                return;
+            }
+        };
+
+        for step in resolution {
+            self.output.print(step);
+        }
+        if let Some(builtin) = self.builtin_commands.get_value_by_path(&builtin_path) {
+            let help = builtin
+                .help()
+                .render(&builtin_path, &self.shortcuts_for(&builtin_path));
+            self.output.print(help);
+        }
+    }
+
+    pub fn builtin_documentation() -> Vec<CommandDoc> {
+        let mut builtins: PathTree<Command> = PathTree::new();
+        let builtin_shortcuts =
+            Interpreter::set_all_builtins(&mut builtins, &mut Output::new(OutputMode::Text, false));
+
+        let mut documentation: Vec<CommandDoc> = builtins
+            .active_entries()
+            .filter(|(path, _)| !TreePath::is_path_a_shortcut(path))
+            .map(|(path, builtin)| CommandDoc {
+                shortcuts: builtin_shortcuts
+                    .iter()
+                    .filter(|(_, for_builtin)| **for_builtin == path)
+                    .map(|(shortcut, _)| shortcut.clone())
+                    .collect(),
+                category: builtin.category(),
+                help: builtin.help(),
+                path,
+            })
+            .collect();
+        documentation
+            .sort_by(|left, right| (left.category, &left.path).cmp(&(right.category, &right.path)));
+
+        documentation
+    }
+
+    fn generate_documentation(&mut self, directory: String) {
+        match docs::write_documentation(
+            &self.resolve_path(&directory),
+            &Interpreter::builtin_documentation(),
+        ) {
+            Ok(written) => {
+                for file in written {
+                    self.output.info(format!("wrote {}", file.display()));
+                }
+            }
+            Err(the_err) => self.report_error(format!(
+                "couldn't write the documentation to {}: {}",
+                directory, the_err
+            )),
+        }
+    }
+
+    fn show_help(&mut self, topic: Option<String>) {
+        let topic = match topic {
+            Some(topic) => topic,
+            None => {
+                let mut text: Vec<String> = vec![];
+                if let Some(basics) = config::get_help_topic("basics") {
+                    text.push(format!("\n{}\n---", basics.text));
+                }
+                text.push(String::from("More help is available on these topics:"));
+                for topic in config::get_help_topics() {
+                    text.push(format!(
+                        "- {}: {};",
+                        self.output.highlight(topic.name),
+                        topic.summary
+                    ));
+                }
+                text.push(format!(
+                    "- {}: a short summary of every command;",
+                    self.output.highlight("commands")
+                ));
+                text.push(String::from("Run \"help topic <ARG>\" to read about one of them, for example:\n<<VIO>> help topic shortcuts"));
+                self.output.print(text.join("\n"));
+                return;
+            }
+        };
+
+        if let Some(help_topic) = config::get_help_topic(&topic) {
+            self.output.print(format!(
+                "\n==={}===\n{}\n",
+                clone_uppercased(help_topic.name),
+                help_topic.text
+            ));
+            return;
+        }
+        if topic.trim().eq_ignore_ascii_case("commands") {
+            let mut categories: BTreeMap<CommandCategory, Vec<(String, &'static str)>> =
+                BTreeMap::new();
+            for (path, builtin) in self.builtin_commands.active_entries() {
+                if self.is_listed_builtin(&path) {
+                    categories
+                        .entry(builtin.category())
+                        .or_default()
+                        .push((path, builtin.help().summary));
+                }
+            }
+            let mut listing: Vec<String> = vec![];
+            for (category, commands) in categories {
+                listing.push(format!("\n[{}]", category.name()));
+                for (path, summary) in commands {
+                    listing.push(format!(
+                        "- {}: {}",
+                        self.output.highlight_path(&path),
+                        summary
+                    ));
+                }
+            }
+            self.output.print(listing.join("\n"));
+            return;
+        }
+        if let Ok(found) = self.builtin_commands.resolve(&topic) {
+            let builtin_path = found.path;
+            self.explain_command(&builtin_path);
             return;
         }
 
-        println!(
-            "{}",
-            self.builtin_commands
-                .get_by_path(command)
-                .unwrap()
-                .to_owned()
-                .value
-                .unwrap()
-                .help()
-        );
+        let topics: Vec<&str> = config::get_help_topics()
+            .iter()
+            .map(|topic| topic.name)
+            .chain(std::iter::once("commands"))
+            .collect();
+        self.report_error(format!(
+            "there's no help topic or command called \"{}\". The topics are: {}.",
+            topic,
+            topics.join(", ")
+        ));
     }
 
     fn add_alias(&mut self, alias: String, for_builtin: String) {
         if !self.builtin_commands.is_node_active(&for_builtin) {
-            println!(
-                "ERROR: Can't set alias, builtin command [{}] does not exist!",
+            self.report_error(format!(
+                "Can't set alias, builtin command [{}] does not exist!",
                 for_builtin
-            );
+            ));
             return;
         }
 
         if self.builtin_commands.is_node_active(&alias) {
-            println!("ERROR: can't set this alias: [{}] is an existing builtin command name. Choose a different name for the alias.", alias);
+            self.report_error(format!("can't set this alias: [{}] is an existing builtin command name. Choose a different name for the alias.", alias));
             return;
         }
 
-        if self.aliases_for_builtins.is_node_active(&alias) {
-            println!("ERROR: Can't set this alias, alias [{}] already exists. Remove the existing one first!", alias);
+        if self.user_config.aliases.is_node_active(&alias) {
+            self.report_error(format!(
+                "Can't set this alias, alias [{}] already exists. Remove the existing one first!",
+                alias
+            ));
             return;
         }
 
         if TreePath::count_x_nodes_for_path(&alias, "<ARG>")
             != TreePath::count_x_nodes_for_path(&for_builtin, "<ARG>")
         {
-            println!(
-                "ERROR: alias and the builtin command have to have an equal number of arguments!"
-            );
+            self.report_error(String::from(
+                "alias and the builtin command have to have an equal number of arguments!",
+            ));
             return;
         }
 
-        self.aliases_for_builtins
-            .set_by_path(for_builtin, alias.as_str());
+        self.make_change(Change::AddAlias { alias, for_builtin });
+    }
+
+    fn check_alias_removable(&self, alias: &str) -> Result<(), String> {
+        if self.builtin_commands.is_node_active(alias) {
+            return Err(String::from(
+                "you can't remove a builtin command. Choose an alias to remove instead.",
+            ));
+        }
+
+        if !self.user_config.aliases.is_node_active(alias) {
+            return Err(format!(
+                "alias {} does not exist. Can't remove alias which doesn't exist.",
+                alias
+            ));
+        }
+
+        Ok(())
     }
 
     fn remove_alias(&mut self, alias: String) {
-        if self.builtin_commands.is_node_active(&alias) {
-            println!(
-                "ERROR: you can't remove a builtin command. Choose an alias to remove instead."
-            );
+        if let Err(the_err) = self.check_alias_removable(&alias) {
+            self.report_error(the_err);
             return;
         }
 
-        if !self.aliases_for_builtins.is_node_active(&alias) {
-            println!(
-                "ERROR: alias {} does not exist. Can't remove alias which doesn't exist.",
-                &alias
-            );
+        let for_builtin = self
+            .user_config
+            .aliases
+            .get_value_by_path(&alias)
+            .cloned()
+            .unwrap_or_default();
+        if self.make_change(Change::RemoveAlias {
+            alias: alias.clone(),
+            for_builtin,
+        }) {
+            self.output.print(format!("Removed alias {}.", alias));
+        }
+    }
+
+    fn remove_config_file(&mut self) -> Result<(), InterpretationError> {
+        let config_name = self.config_file().display().to_string();
+        std::fs::remove_file(&config_name).map_err(|the_err| file_error(&config_name, the_err))?;
+        self.config_empty_on_boot = true;
+        self.config_load_failed = false;
+        self.output.print(format!("Deleted {}.", config_name));
+
+        Ok(())
+    }
+
+    fn add_synonym(&mut self, synonym: String, for_word: String) {
+        if TreePath::create_path(&synonym).len() != 1 || TreePath::create_path(&for_word).len() != 1
+        {
+            self.report_error(String::from(
+                "both the synonym and the word it stands for have to be single words!",
+            ));
             return;
         }
+        let synonym = TreePath::prettify(&synonym);
+        let for_word = TreePath::prettify(&for_word);
 
-        match self.aliases_for_builtins.drop_by_path(&alias) {
-            Ok(PathTreeOk::DropOk) => (),
-            Err(PathTreeErr::DropNodeDoesNotExist) => {
-                println!("ERROR: PathTree: node [{}] does not exist!", &alias);
-            }
-            Err(PathTreeErr::DropNodeIsNull) => {
-                println!("ERROR: this node is a null node. Null nodes can't be explicitly deleted by a user.");
+        if for_word == "<ARG>" || !self.builtin_commands.uses_word(&for_word) {
+            self.report_error(format!(
+                "can't add synonym, no builtin command uses the word [{}]!",
+                for_word
+            ));
+            return;
+        }
+
+        if self.builtin_commands.uses_word(&synonym)
+            || self.builtin_commands.uses_word(&synonym.to_lowercase())
+        {
+            self.report_error(format!("can't add synonym, [{}] is already used by builtin commands. Choose a different word for the synonym.", synonym));
+            return;
+        }
+
+        if let Some(existing) = self
+            .builtin_commands
+            .synonyms()
+            .get(&synonym.to_lowercase())
+        {
+            self.report_error(format!("can't add synonym, [{}] is already a synonym for [{}]. Remove the existing one first!", synonym, existing));
+            return;
+        }
+
+        self.make_change(Change::AddSynonym { synonym, for_word });
+    }
+
+    fn remove_synonym(&mut self, synonym: String) {
+        let synonym = TreePath::prettify(&synonym);
+        let for_word = match self
+            .builtin_commands
+            .synonyms()
+            .get(&synonym.to_lowercase())
+        {
+            Some(for_word) => for_word.to_owned(),
+            None => {
+                self.report_error(format!(
+                    "synonym [{}] does not exist. Can't remove a synonym which doesn't exist.",
+                    synonym
+                ));
+                return;
             }
         };
+
+        self.make_change(Change::RemoveSynonym { synonym, for_word });
     }
 
-    pub fn run_repl(&mut self) {
-        println!(
-            "Welcome to {} the command interpreter!",
-            clone_uppercased(&config::get_violet_name())
-        );
-        println!(
-            "{}'s version is {};",
-            clone_uppercased(&config::get_violet_name()),
-            config::get_violet_version()
-        );
-        println!("Created by {}.", config::get_violet_author());
-        println!(
-            "To get help with the basics of Violet, type: \nhelp\n\tor\n[h]\n and press <ENTER>."
-        );
+    fn list_synonyms(&mut self) {
+        let synonyms = self.builtin_commands.synonyms();
+        if synonyms.is_empty() {
+            self.output.print(String::from("No synonyms defined!"));
+            return;
+        }
 
-        loop {
-            let user_input = input::get_user_input(config::get_violet_prompt());
-            if user_input.is_empty() {
-                continue;
-            }
+        let mut words: Vec<&str> = synonyms.iter().map(|(_, word)| word).collect();
+        words.sort_unstable();
+        words.dedup();
 
-            let command_to_invoke: String = match self
-                .aliases_for_builtins
-                .get_command_and_args_from_path(&user_input)
-            {
-                None => user_input,
-                Some((path, args)) => {
-                    if self.aliases_for_builtins.is_node_active(&path) {
-                        TreePath::reconstruct_argumented_path(
-                            &self
-                                .aliases_for_builtins
-                                .get_by_path(&path)
-                                .unwrap()
-                                .to_owned()
-                                .value
-                                .unwrap(),
-                            args,
-                        )
-                    } else {
-                        user_input
-                    }
+        let mut listing = vec![String::from("Known synonyms:\n")];
+        for word in words {
+            listing.push(format!(
+                "- {}: {};",
+                self.output.highlight(word),
+                synonyms
+                    .synonyms_for(word)
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            ));
+        }
+        self.output.print(listing.join("\n"));
+    }
+
+    fn register_user_shortcut(
+        &mut self,
+        shortcut: &str,
+        for_builtin: &str,
+    ) -> Result<String, PathTreeErr> {
+        let for_builtin = self.canonical_path(&TreePath::prettify(for_builtin));
+        let builtin = match self.builtin_commands.get_value_by_path(&for_builtin) {
+            Some(builtin) if !TreePath::is_path_a_shortcut(&for_builtin) => builtin.clone(),
+            _ => return Err(PathTreeErr::NodeDoesNotExist { path: for_builtin }),
+        };
+
+        let shortcut_path = TreePath::create_shortcut_path(shortcut, &for_builtin)?;
+        if self.builtin_commands.is_node_active(&shortcut_path) {
+            return Err(PathTreeErr::ShortcutTaken {
+                shortcut: shortcut_path,
+            });
+        }
+
+        self.builtin_commands
+            .set_by_path(builtin.clone(), &shortcut_path)?;
+        let normalization = builtin.normalization();
+        if normalization != Normalization::default() {
+            self.builtin_commands
+                .set_normalization(&shortcut_path, normalization)?;
+        }
+        self.user_config
+            .shortcuts
+            .insert(shortcut_path.clone(), for_builtin);
+
+        Ok(shortcut_path)
+    }
+
+    fn set_shortcut(&mut self, shortcut: String, for_builtin: String) {
+        match self.register_user_shortcut(&shortcut, &for_builtin) {
+            Ok(shortcut) => {
+                let for_builtin = self
+                    .user_config
+                    .shortcuts
+                    .get(&shortcut)
+                    .cloned()
+                    .unwrap_or_default();
+                self.changes.record(Change::SetShortcut {
+                    shortcut,
+                    for_builtin,
+                });
+            }
+            Err(the_err) => match the_err {
+                PathTreeErr::ShortcutTaken { shortcut } => {
+                    let taken_by = self
+                        .builtin_shortcuts
+                        .get(&shortcut)
+                        .or_else(|| self.user_config.shortcuts.get(&shortcut))
+                        .cloned()
+                        .unwrap_or_else(|| String::from("another command"));
+                    self.report_error(format!("can't set shortcut, {} is already taken by [{}]. Choose a different shortcut.", shortcut, taken_by));
                 }
-            };
+                PathTreeErr::NodeDoesNotExist { path } => self.report_error(format!(
+                    "can't set shortcut, builtin command [{}] does not exist!",
+                    path
+                )),
+                the_err => self.report_error(format!("can't set shortcut: {}", the_err)),
+            },
+        }
+    }
 
-            match self
-                .builtin_commands
-                .get_command_and_args_from_path(&command_to_invoke)
+    fn remove_shortcut(&mut self, shortcut: String) {
+        let shortcut = TreePath::prettify(&shortcut);
+        let mut candidates: Vec<String> = self
+            .user_config
+            .shortcuts
+            .keys()
+            .filter(|path| {
+                **path == shortcut || TreePath::get_shortcut_name(path).as_ref() == Some(&shortcut)
+            })
+            .cloned()
+            .collect();
+
+        if candidates.is_empty() {
+            if self.builtin_shortcuts.contains_key(&shortcut)
+                || self
+                    .builtin_shortcuts
+                    .keys()
+                    .any(|path| TreePath::get_shortcut_name(path).as_ref() == Some(&shortcut))
             {
-                None => {
-                    println!(
-                        "{}: command does not exist.",
-                        TreePath::prettify(command_to_invoke.as_str())
-                    );
-                }
-                Some((path, args)) => {
-                    if self.builtin_commands.is_node_active(&path) {
-                        let node = self.builtin_commands.get_by_path(&path).unwrap();
-                        match node.clone().value.unwrap().execute(args) {
-                            Ok(InterpretedCommand::DoNothing) => (),
-                            Ok(InterpretedCommand::ListAvailableCommands) => self.list_available_commands(),
-                            Ok(InterpretedCommand::Exit { exit_message}) => self.exit(exit_message),
-                            Ok(InterpretedCommand::AddAlias {alias, for_builtin}) => self.add_alias(alias, for_builtin),
-                            Ok(InterpretedCommand::RemoveAlias {alias}) => self.remove_alias(alias),
-                            Ok(InterpretedCommand::ExplainCommand {command}) => self.explain_command(&command),
-
-                            Err(InterpretationError::ArgSpecifierMisused) => println!("{}", config::get_argspec_misused_error_message()),
-                            Err(InterpretationError::ArgumentEmpty {argument_name}) => println!("ERROR: Argument named [{}] is empty, which is not allowed in this context!", argument_name),
-                        }
-                    } else {
-                        println!(
-                            "{}: command does not exist.",
-                            TreePath::prettify(command_to_invoke.as_str())
-                        );
-                    }
-                }
+                self.report_error(String::from("you can't remove a builtin shortcut. Choose a shortcut you've set yourself instead."));
+            } else {
+                self.report_error(format!(
+                    "shortcut {} does not exist. Can't remove a shortcut which doesn't exist.",
+                    shortcut
+                ));
             }
+            return;
+        }
+        if candidates.len() > 1 {
+            self.report_error(format!("there are several shortcuts named {}: [{}]. Please specify the one to remove with its <ARG>s.", shortcut,
+                candidates.join("], ["))
+            );
+            return;
         }
+
+        let shortcut_path = candidates.remove(0);
+        let for_builtin = self
+            .user_config
+            .shortcuts
+            .get(&shortcut_path)
+            .cloned()
+            .unwrap_or_default();
+        self.make_change(Change::RemoveShortcut {
+            shortcut: shortcut_path,
+            for_builtin,
+        });
     }
+
+    fn apply_change(&mut self, change: &Change) -> Result<(), String> {
+        match change.clone() {
+            Change::AddAlias { alias, for_builtin } => self
+                .user_config
+                .aliases
+                .set_by_path(for_builtin, &alias)
+                .map(|_| ())
+                .map_err(|the_err| format!("couldn't set alias [{}]: {}", alias, the_err)),
+            Change::RemoveAlias { alias, .. } => self
+                .user_config
+                .aliases
+                .drop_by_path(&alias)
+                .map(|_| ())
+                .map_err(|the_err| format!("couldn't remove alias [{}]: {}", alias, the_err)),
+            Change::AddSynonym { synonym, for_word } => {
+                self.builtin_commands
+                    .synonyms_mut()
+                    .add(&synonym, &for_word);
+                self.synonyms_changed = true;
+                Ok(())
+            }
+            Change::RemoveSynonym { synonym, .. } => {
+                self.builtin_commands.synonyms_mut().remove(&synonym);
+                self.synonyms_changed = true;
+                Ok(())
+            }
+            Change::SetShortcut {
+                shortcut,
+                for_builtin,
+            } => {
+                let name = TreePath::get_shortcut_name(&shortcut).unwrap_or(shortcut);
+                self.register_user_shortcut(&name, &for_builtin)
+                    .map(|_| ())
+                    .map_err(|the_err| format!("couldn't set shortcut {}: {}", name, the_err))
+            }
+            Change::RemoveShortcut { shortcut, .. } => {
+                self.user_config.shortcuts.remove(&shortcut);
+                self.builtin_commands
+                    .drop_by_path(&shortcut)
+                    .map(|_| ())
+                    .map_err(|the_err| {
+                        format!("couldn't remove shortcut {}: {}", shortcut, the_err)
+                    })
+            }
+        }
+    }
+
+    fn make_change(&mut self, change: Change) -> bool {
+        match self.apply_change(&change) {
+            Ok(()) => {
+                self.changes.record(change);
+                true
+            }
+            Err(the_err) => {
+                self.report_error(the_err);
+                false
+            }
+        }
+    }
+
+    fn undo_last_change(&mut self) {
+        let change = match self.changes.to_undo() {
+            Some(change) => change.clone(),
+            None => {
+                self.report_error(String::from(
+                    "there's nothing to undo, no aliases, shortcuts or synonyms have changed in this session",
+                ));
+                return;
+            }
+        };
+
+        match self.apply_change(&change.inverse()) {
+            Ok(()) => {
+                self.changes.mark_undone();
+                self.output
+                    .print(format!("Undid the last change: {}.", change.describe()));
+            }
+            Err(the_err) => {
+                self.report_error(format!("couldn't undo the last change: {}", the_err))
+            }
+        }
+    }
+
+    fn redo_last_change(&mut self) {
+        let change = match self.changes.to_redo() {
+            Some(change) => change.clone(),
+            None => {
+                self.report_error(String::from("there's nothing to redo, undo a change first"));
+                return;
+            }
+        };
+
+        match self.apply_change(&change) {
+            Ok(()) => {
+                self.changes.mark_redone();
+                self.output
+                    .print(format!("Redid the change: {}.", change.describe()));
+            }
+            Err(the_err) => self.report_error(format!("couldn't redo the change: {}", the_err)),
+        }
+    }
+
+    fn show_change_log(&mut self) {
+        if self.changes.done().is_empty() && self.changes.undone().is_empty() {
+            self.output.print(String::from(
+                "No changes yet! Adding or removing aliases, shortcuts and synonyms shows up here.",
+            ));
+            return;
+        }
+
+        let mut listing = vec![];
+        if !self.changes.done().is_empty() {
+            listing.push(String::from("Changes in this session, the latest first:\n"));
+            for change in self.changes.done().iter().rev() {
+                listing.push(format!("- {};", change.describe()));
+            }
+        }
+        if !self.changes.undone().is_empty() {
+            if !listing.is_empty() {
+                listing.push(String::new());
+            }
+            listing.push(String::from(
+                "Undone changes, the next one to redo first:\n",
+            ));
+            for change in self.changes.undone().iter().rev() {
+                listing.push(format!("- {};", change.describe()));
+            }
+        }
+        self.output.print(listing.join("\n"));
+    }
+
+    fn list_shortcut_collisions(&mut self) {
+        let mut derived: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (path, _) in self.builtin_commands.active_entries() {
+            if !self.is_listed_builtin(&path) {
+                continue;
+            }
+            if let Ok(shortcut) = TreePath::create_shortcut(&path, 1) {
+                derived.entry(shortcut).or_default().push(path);
+            }
+        }
+
+        let collisions: Vec<(&String, &Vec<String>)> = derived
+            .iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .collect();
+        if collisions.is_empty() {
+            self.output.print(String::from(
+                "No shortcut collisions, every command's first letters are unique!",
+            ));
+            return;
+        }
+
+        let mut listing = vec![String::from("Commands sharing the same first letters:\n")];
+        for (shortcut, paths) in collisions {
+            listing.push(format!("{}:", self.output.highlight(shortcut)));
+            for path in paths {
+                listing.push(format!(
+                    "- {} => {};",
+                    self.output.highlight_path(path),
+                    self.shortcuts_for(path).join(", ")
+                ));
+            }
+        }
+        self.output.print(listing.join("\n"));
+    }
+
+    fn start_stopwatch(&mut self) {
+        if let Some(started) = self.stopwatch {
+            let running =
+                Duration::from_std(started.elapsed()).unwrap_or_else(|_| Duration::zero());
+            self.report_error(format!(
+                "the stopwatch has already been running for {}. Stop it first!",
+                time::format_duration(running)
+            ));
+            return;
+        }
+
+        self.stopwatch = Some(Instant::now());
+        self.output.print(String::from(
+            "The stopwatch is running! Run \"stop stopwatch\" to stop it.",
+        ));
+    }
+
+    fn stop_stopwatch(&mut self) {
+        match self.stopwatch.take() {
+            Some(started) => {
+                let elapsed =
+                    Duration::from_std(started.elapsed()).unwrap_or_else(|_| Duration::zero());
+                self.output.print(format!(
+                    "The stopwatch stopped at {}.",
+                    time::format_duration(elapsed)
+                ));
+            }
+            None => self.report_error(String::from(
+                "the stopwatch isn't running. Start it with \"start stopwatch\" first!",
+            )),
+        }
+    }
+
+    fn remind(&mut self, after: Duration, text: String) {
+        let due = Local::now() + after;
+        self.output.print(format!(
+            "I'll remind you to {} at {}.",
+            text,
+            due.format("%I:%M:%S %p")
+        ));
+
+        let notifier = self.output.notifier();
+        let reminder = format!("Reminder: {}!", text);
+        std::thread::spawn(move || {
+            std::thread::sleep(after.to_std().unwrap_or_default());
+            notifier.notify(Severity::Notice, reminder);
+        });
+    }
+
+    fn resolve_path(&self, path: &str) -> PathBuf {
+        let home = std::env::var_os("HOME").map(PathBuf::from);
+        match (path.strip_prefix('~'), home) {
+            (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+                home.join(rest.trim_start_matches('/'))
+            }
+            _ => self.cwd.join(path),
+        }
+    }
+
+    fn show_file(&mut self, path: String) -> Result<(), InterpretationError> {
+        let file = self.resolve_path(&path);
+        if file.is_dir() {
+            return Err(InterpretationError::ArgumentInvalid {
+                argument: path,
+                reason: String::from(
+                    "it's a folder, use \"list files in <ARG>\" to see what's inside",
+                ),
+            });
+        }
+
+        let contents = std::fs::read(&file).map_err(|the_err| file_error(&path, the_err))?;
+        match String::from_utf8(contents) {
+            Ok(contents) if contents.trim().is_empty() => {
+                self.output.print(format!("{} is empty.", path))
+            }
+            Ok(contents) => self.output.print(contents.trim_end().to_owned()),
+            Err(_) => {
+                return Err(InterpretationError::ArgumentInvalid {
+                    argument: path,
+                    reason: String::from("it isn't a text file"),
+                })
+            }
+        }
+
+        Ok(())
+    }
+
+    fn list_files(&mut self, folder: String) -> Result<(), InterpretationError> {
+        let directory = self.resolve_path(&folder);
+        let entries =
+            std::fs::read_dir(&directory).map_err(|the_err| file_error(&folder, the_err))?;
+        let mut folders: Vec<String> = vec![];
+        let mut files: Vec<String> = vec![];
+        for entry in entries {
+            let entry = entry.map_err(|the_err| file_error(&folder, the_err))?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if entry.path().is_dir() {
+                folders.push(format!("{}/", name));
+            } else {
+                files.push(name);
+            }
+        }
+        folders.sort();
+        files.sort();
+
+        let shown = directory.canonicalize().unwrap_or(directory);
+        if folders.is_empty() && files.is_empty() {
+            self.output.print(format!("{} is empty.", shown.display()));
+            return Ok(());
+        }
+
+        let mut listing = vec![format!("Files in {}:\n", shown.display())];
+        for name in folders.into_iter().chain(files) {
+            listing.push(format!("- {}", name));
+        }
+        self.output.print(listing.join("\n"));
+
+        Ok(())
+    }
+
+    fn copy_file(
+        &mut self,
+        from: String,
+        to: String,
+        overwrite: bool,
+    ) -> Result<(), InterpretationError> {
+        let source = self.resolve_path(&from);
+        if source.is_dir() {
+            return Err(InterpretationError::ArgumentInvalid {
+                argument: from,
+                reason: String::from("it's a folder, and Violet only copies files"),
+            });
+        }
+        if !source.exists() {
+            return Err(InterpretationError::PathNotFound { path: from });
+        }
+
+        let mut destination = self.resolve_path(&to);
+        if destination.is_dir() {
+            if let Some(name) = source.file_name() {
+                destination.push(name);
+            }
+        }
+        if destination.canonicalize().ok() == source.canonicalize().ok() {
+            return Err(InterpretationError::ArgumentInvalid {
+                argument: to,
+                reason: String::from("a file can't be copied onto itself"),
+            });
+        }
+        if destination.exists() && !overwrite {
+            return self.ask_confirmation(
+                format!("{} already exists. Replace it?", destination.display()),
+                InterpretedCommand::CopyFile {
+                    from,
+                    to,
+                    overwrite: true,
+                },
+            );
+        }
+
+        let copied =
+            std::fs::copy(&source, &destination).map_err(|the_err| file_error(&to, the_err))?;
+        self.output.print(format!(
+            "Copied {} to {} ({} bytes).",
+            from,
+            destination.display(),
+            copied
+        ));
+
+        Ok(())
+    }
+
+    fn go_to_folder(&mut self, folder: String) -> Result<(), InterpretationError> {
+        let directory = self
+            .resolve_path(&folder)
+            .canonicalize()
+            .map_err(|the_err| file_error(&folder, the_err))?;
+        if !directory.is_dir() {
+            return Err(InterpretationError::ArgumentInvalid {
+                argument: folder,
+                reason: String::from("it's a file, not a folder"),
+            });
+        }
+
+        self.cwd = directory;
+        self.output
+            .print(format!("You're in {} now.", self.cwd.display()));
+
+        Ok(())
+    }
+
+    fn calculate(&mut self, expression: String) {
+        let mut variables = vec![
+            ("pi", Number::Float(std::f64::consts::PI)),
+            ("e", Number::Float(std::f64::consts::E)),
+        ];
+        if let Some(last_result) = self.last_result {
+            variables.push(("ans", last_result));
+            variables.push(("result", last_result));
+        }
+
+        match calc::evaluate(&expression, &variables) {
+            Ok(result) => {
+                self.last_result = Some(result);
+                self.output.set_result(result);
+                self.output.print(format!("{} = {}", expression, result));
+            }
+            Err(reason) => {
+                let message = self
+                    .locale
+                    .format(Message::ArgumentInvalid, &[&expression, &reason]);
+                self.report_error(message)
+            }
+        }
+    }
+
+    fn resolve_command_path(&self, command: &str) -> Result<String, String> {
+        let command_to_invoke = self.expand_alias(command.to_owned())?;
+        match self.builtin_commands.resolve(&command_to_invoke) {
+            Ok(PathMatch { path, .. }) => Ok(self.builtin_path(&path)),
+            Err(PathTreeErr::NoMatch { path }) => {
+                Err(self.locale.format(Message::CommandDoesNotExist, &[&path]))
+            }
+            Err(the_err) => Err(format!("{}.", the_err)),
+        }
+    }
+
+    fn check_job_command(&self, command: &str) -> Result<(), String> {
+        let path = self.resolve_command_path(command)?;
+        match path.as_str() {
+            "exit" | "run <ARG> in background" | "every <ARG> run <ARG>" | "at <ARG> run <ARG>" => {
+                Err(format!(
+                    "[{}] can't run as a job: jobs can't exit Violet or start other jobs.",
+                    path
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn schedule_job(&mut self, command: String, schedule: JobSchedule) {
+        if let Err(the_err) = self.check_job_command(&command) {
+            self.report_error(the_err);
+            return;
+        }
+
+        let job = self.scheduler.add(command, schedule, Local::now());
+        self.output
+            .print(format!("Started job {}: {}.", job.id, job.describe()));
+    }
+
+    fn list_jobs(&mut self) {
+        let jobs = self.scheduler.jobs();
+        if jobs.is_empty() {
+            self.output.print(String::from(
+                "No jobs yet! You can start one with \"run <ARG> in background\".",
+            ));
+            return;
+        }
+
+        let mut listing = vec![String::from("Your jobs:\n")];
+        for job in jobs {
+            listing.push(format!("- job {}: {};", job.id, job.describe()));
+        }
+        self.output.print(listing.join("\n"));
+    }
+
+    fn cancel_job(&mut self, id: usize) {
+        match self.scheduler.cancel(id) {
+            Some(job) => {
+                self.output
+                    .print(format!("Cancelled job {}: {}.", job.id, job.describe()))
+            }
+            None => self.report_error(format!(
+                "job {} does not exist. Run \"list jobs\" to see your jobs.",
+                id
+            )),
+        }
+    }
+
+    pub fn run_job(&mut self, job: Job) {
+        let last_status = self.last_status;
+        let prompt = self.output.interrupt_prompt();
+        if self.output.mode() == OutputMode::Text {
+            self.output
+                .notice(format!("job {} runs \"{}\":", job.id, job.command));
+        }
+
+        let pending_confirmation = self.pending_confirmation.take();
+        self.output.begin_command(&job.command);
+        self.output.set_job(job.id);
+        match self.check_job_command(&job.command) {
+            Ok(()) => self.dispatch(job.command),
+            Err(the_err) => self.report_error(the_err),
+        }
+        if self.pending_confirmation.is_some() {
+            self.report_error(format!(
+                "job {} needs your confirmation, but jobs can't ask for it. Nothing has changed.",
+                job.id
+            ));
+        }
+        self.pending_confirmation = pending_confirmation;
+        if let Some(record) = self.output.end_command(self.last_status == 0) {
+            self.record(record);
+        }
+
+        self.last_status = last_status;
+        self.output.restore_prompt(prompt);
+    }
+
+    fn run_due_jobs(&mut self) {
+        for job in self.scheduler.due_jobs(Local::now()) {
+            self.run_job(job);
+        }
+    }
+
+    fn abandon_jobs(&mut self) {
+        let abandoned = self.scheduler.clear();
+        if !abandoned.is_empty() {
+            self.output.warning(format!(
+                "{} unfinished job{} won't run, because Violet is exiting.",
+                abandoned.len(),
+                if abandoned.len() == 1 { "" } else { "s" }
+            ));
+        }
+    }
+
+    fn lock(shared: &Mutex<Interpreter>) -> MutexGuard<'_, Interpreter> {
+        shared
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn run_repl(self) -> i32 {
+        let interactive = self.output.mode() == OutputMode::Text && input::is_interactive();
+        let scheduler = Arc::clone(&self.scheduler);
+        let shared = Arc::new(Mutex::new(self));
+        let for_jobs = Arc::clone(&shared);
+        Scheduler::spawn(scheduler, move |job| {
+            Interpreter::lock(&for_jobs).run_job(job)
+        });
+
+        Interpreter::run_input(&shared, &mut std::io::stdin().lock(), interactive)
+    }
+
+    pub fn run_input(
+        shared: &Mutex<Interpreter>,
+        reader: &mut impl BufRead,
+        interactive: bool,
+    ) -> i32 {
+        {
+            let mut interpreter = Interpreter::lock(shared);
+            if interactive && !interpreter.output.is_quiet() {
+                let banner = appearance::paint(
+                    &interpreter.banner(),
+                    interpreter.user_config.appearance.theme.banner_colour(),
+                    interpreter.output.colours_enabled(),
+                );
+                interpreter.output.print(banner);
+            }
+        }
+
+        loop {
+            let (prompt, continuation_prompt) = {
+                let interpreter = Interpreter::lock(shared);
+                let prompts = if interactive {
+                    (interpreter.prompt(), interpreter.continuation_prompt())
+                } else {
+                    (String::new(), String::new())
+                };
+                interpreter.output.set_prompt(&prompts.0);
+                prompts
+            };
+            let user_input = input::get_command(reader, &prompt, &continuation_prompt);
+
+            let mut interpreter = Interpreter::lock(shared);
+            interpreter.output.set_prompt("");
+            let user_input = match user_input {
+                Ok(Some(user_input)) => user_input,
+                Ok(None) if interactive => {
+                    interpreter.output.print(String::new());
+                    interpreter.exit();
+                    return interpreter.last_status;
+                }
+                Ok(None) => {
+                    interpreter.run_due_jobs();
+                    interpreter.drop_unanswered_confirmation();
+                    interpreter.abandon_jobs();
+                    interpreter.save_state();
+                    return interpreter.last_status;
+                }
+                Err(the_err) => {
+                    interpreter
+                        .report_error(format!("couldn't read the next command: {}", the_err));
+                    interpreter.abandon_jobs();
+                    interpreter.save_state();
+                    return interpreter.last_status;
+                }
+            };
+            if user_input.is_empty() {
+                continue;
+            }
+
+            interpreter.interpret(user_input);
+            if interpreter.exiting {
+                return interpreter.last_status;
+            }
+            interpreter.run_due_jobs();
+        }
+    }
+
+    pub fn run_commands(&mut self, commands: &[String]) -> i32 {
+        for command in commands {
+            self.interpret(command.clone());
+            if self.exiting {
+                return self.last_status;
+            }
+            if self.last_status != 0 {
+                break;
+            }
+            self.run_due_jobs();
+        }
+
+        let status = self.last_status;
+        self.drop_unanswered_confirmation();
+        self.abandon_jobs();
+        self.save_state();
+        status.max(self.last_status)
+    }
+
+    pub fn last_status(&self) -> i32 {
+        self.last_status
+    }
+
+    pub fn interpret(&mut self, user_input: String) {
+        self.interpret_line(user_input);
+    }
+
+    fn interpret_line(&mut self, user_input: String) -> Option<CommandRecord> {
+        let recording = self.recorder.is_some();
+        let record = self.run_line(user_input)?;
+        if recording {
+            self.record(record.clone());
+        }
+
+        Some(record)
+    }
+
+    fn run_line(&mut self, user_input: String) -> Option<CommandRecord> {
+        self.last_status = 0;
+        self.output.begin_command(&user_input);
+        match self.pending_confirmation.take() {
+            Some(action) => self.answer_confirmation(action, &user_input),
+            None => self.dispatch(user_input),
+        }
+        self.output.end_command(self.last_status == 0)
+    }
+
+    fn record(&mut self, record: CommandRecord) {
+        let recorder = match &mut self.recorder {
+            Some(recorder) => recorder,
+            None => return,
+        };
+        if let Err(the_err) = recorder.write(record) {
+            let path = recorder.path().display().to_string();
+            self.recorder = None;
+            self.output.warning(format!(
+                "couldn't write to the transcript {}, so the recording has stopped: {}",
+                path, the_err
+            ));
+        }
+    }
+
+    fn start_recording(
+        &mut self,
+        path: String,
+        overwrite: bool,
+    ) -> Result<(), InterpretationError> {
+        if let Some(recorder) = &self.recorder {
+            let message = format!(
+                "already recording to {}. Run \"stop recording\" first.",
+                recorder.path().display()
+            );
+            self.report_error(message);
+            return Ok(());
+        }
+
+        let file = self.resolve_path(&path);
+        if file.is_dir() {
+            return Err(InterpretationError::ArgumentInvalid {
+                argument: path,
+                reason: String::from("it's a folder, the transcript has to be a file"),
+            });
+        }
+        if file.exists() && !overwrite {
+            return self.ask_confirmation(
+                format!("{} already exists. Replace it?", file.display()),
+                InterpretedCommand::StartRecording {
+                    path,
+                    overwrite: true,
+                },
+            );
+        }
+
+        let recorder = Recorder::start(&file).map_err(|the_err| file_error(&path, the_err))?;
+        self.output.print(format!(
+            "Recording to {}. Run \"stop recording\" when you're done.",
+            recorder.path().display()
+        ));
+        self.recorder = Some(recorder);
+
+        Ok(())
+    }
+
+    fn stop_recording(&mut self) {
+        match self.recorder.take() {
+            Some(recorder) => self.output.print(format!(
+                "Stopped recording, {} command(s) saved to {}.",
+                recorder.entries(),
+                recorder.path().display()
+            )),
+            None => self.report_error(String::from(
+                "nothing is being recorded. Start a recording with \"start recording to <ARG>\"",
+            )),
+        }
+    }
+
+    fn check_replay_command(&self, input: &str) -> Result<(), String> {
+        match self.resolve_command_path(input) {
+            Ok(path)
+                if matches!(
+                    path.as_str(),
+                    "exit" | "start recording to <ARG>" | "stop recording" | "replay session <ARG>"
+                ) =>
+            {
+                Err(format!("[{}] isn't replayed", path))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn replay_session(&mut self, path: String) -> Result<(), InterpretationError> {
+        let contents = std::fs::read_to_string(self.resolve_path(&path))
+            .map_err(|the_err| file_error(&path, the_err))?;
+        let entries = transcript::parse_transcript(&contents).map_err(|reason| {
+            InterpretationError::ArgumentInvalid {
+                argument: path.clone(),
+                reason,
+            }
+        })?;
+
+        let outer_record = self.output.suspend_command();
+        let pending_confirmation = self.pending_confirmation.take();
+        self.output.set_muted(true);
+        let mut report: Vec<String> = vec![];
+        let (mut replayed, mut differing) = (0, 0);
+        for (number, entry) in entries.into_iter().enumerate() {
+            let input = entry.record.input.clone();
+            if entry.record.job.is_some() {
+                continue;
+            }
+            if let Err(reason) = self.check_replay_command(&input) {
+                report.push(format!(
+                    "Skipped command {}, \"{}\": {}.",
+                    number + 1,
+                    input,
+                    reason
+                ));
+                continue;
+            }
+
+            replayed += 1;
+            let record = self.run_line(input.clone()).unwrap_or_default();
+            let diff = transcript::diff_records(&entry.record, &record);
+            if !diff.is_empty() {
+                differing += 1;
+                report.push(format!("Command {}, \"{}\", differs:", number + 1, input));
+                report.extend(diff);
+            }
+        }
+        if self.pending_confirmation.take().is_some() {
+            report.push(String::from(
+                "The transcript ends with an unanswered question, so that command didn't change anything.",
+            ));
+        }
+        self.output.set_muted(false);
+        self.pending_confirmation = pending_confirmation;
+        self.output.resume_command(outer_record);
+        self.last_status = 0;
+
+        if differing == 0 {
+            report.push(format!(
+                "Replayed {} command(s) from {}, and the output matches the recording.",
+                replayed, path
+            ));
+        }
+        self.output.print(report.join("\n"));
+        if differing > 0 {
+            self.report_error(format!(
+                "the replay differs from the recording in {} of {} commands",
+                differing, replayed
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn set_confirmations(&mut self, enabled: bool) {
+        self.confirmations = enabled;
+    }
+
+    fn check_before_confirming(&self, action: &InterpretedCommand) -> Result<(), String> {
+        match action {
+            InterpretedCommand::RemoveAlias { alias } => self.check_alias_removable(alias),
+            InterpretedCommand::RemoveConfigFile => {
+                let config_name = self.config_file();
+                if !config_name.is_file() {
+                    Err(format!(
+                        "there's no config file {} to delete",
+                        config_name.display()
+                    ))
+                } else if !self.config_load_failed && !self.user_config.is_empty() {
+                    Err(String::from("the config still has aliases, shortcuts or settings in it. Remove them first, so that nothing is lost by accident"))
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn ask_confirmation(
+        &mut self,
+        question: String,
+        action: InterpretedCommand,
+    ) -> Result<(), InterpretationError> {
+        if !self.confirmations {
+            self.output
+                .info(format!("{} Yes, confirmations are off.", question));
+            return self.perform(action);
+        }
+
+        self.output.print(format!("{} (yes/no)", question));
+        self.pending_confirmation = Some(action);
+
+        Ok(())
+    }
+
+    fn answer_confirmation(&mut self, action: InterpretedCommand, answer: &str) {
+        let reply = answer.trim_end_matches(['.', '!']).to_lowercase();
+        match reply.as_str() {
+            "yes" | "y" | "так" => {
+                if let Err(the_err) = self.perform(action) {
+                    self.report_interpretation_error(the_err);
+                }
+            }
+            "no" | "n" | "ні" => self
+                .output
+                .print(String::from("Okay, nothing has changed.")),
+            _ => {
+                self.output.print(String::from(
+                    "Okay, nothing has changed. Running your new command instead.",
+                ));
+                self.dispatch(answer.to_owned());
+            }
+        }
+    }
+
+    fn drop_unanswered_confirmation(&mut self) {
+        if self.pending_confirmation.take().is_some() {
+            self.output.warning(String::from("the last question wasn't answered, so nothing has changed. Run Violet with --yes to answer yes to every question."));
+            self.last_status = 1;
+        }
+    }
+
+    fn expand_alias(&self, user_input: String) -> Result<String, String> {
+        match self.user_config.aliases.resolve(&user_input) {
+            Ok(PathMatch { path, args, .. }) => {
+                match self.user_config.aliases.get_value_by_path(&path) {
+                    Some(builtin) => {
+                        TreePath::reconstruct_argumented_path(builtin, args).map_err(|the_err| {
+                            format!(
+                                "alias [{}] couldn't be expanded into [{}]: {}",
+                                path, builtin, the_err
+                            )
+                        })
+                    }
+                    None => Ok(user_input),
+                }
+            }
+            Err(the_err @ PathTreeErr::AmbiguousMatch { .. }) => Err(format!(
+                "{}. Please quote the arguments to disambiguate.",
+                the_err
+            )),
+            Err(_) => Ok(user_input),
+        }
+    }
+
+    fn builtin_path(&self, path: &str) -> String {
+        self.builtin_shortcuts
+            .get(path)
+            .or_else(|| self.user_config.shortcuts.get(path))
+            .cloned()
+            .unwrap_or_else(|| self.canonical_path(path))
+    }
+
+    fn dispatch(&mut self, user_input: String) {
+        let command_to_invoke = match self.expand_alias(user_input) {
+            Ok(command_to_invoke) => command_to_invoke,
+            Err(the_err) => {
+                self.report_error(the_err);
+                return;
+            }
+        };
+
+        match self.builtin_commands.resolve(&command_to_invoke) {
+            Err(PathTreeErr::NoMatch { path }) => {
+                let message = self.locale.format(Message::CommandDoesNotExist, &[&path]);
+                self.report_error(message);
+            }
+            Err(the_err) => self.report_error(format!("{}.", the_err)),
+            Ok(PathMatch { path, args, .. }) => {
+                let command = self.builtin_path(&path);
+                self.output.set_command(&command, &args);
+                if let Some(builtin) = self.builtin_commands.get_value_by_path(&path) {
+                    let interpreted = builtin.clone().execute(args);
+                    if let Err(the_err) = interpreted.and_then(|command| self.perform(command)) {
+                        self.report_interpretation_error(the_err);
+                    }
+                }
+            }
+        }
+    }
+
+    fn perform(&mut self, command: InterpretedCommand) -> Result<(), InterpretationError> {
+        match command {
+            InterpretedCommand::DoNothing => (),
+            InterpretedCommand::Reply { text } => self.output.print(text),
+            InterpretedCommand::ListAvailableCommands { matching } => {
+                self.list_available_commands(matching)
+            }
+            InterpretedCommand::ListAliases => self.list_aliases(),
+            InterpretedCommand::ShowHelp { topic } => self.show_help(topic),
+            InterpretedCommand::GenerateDocumentation { directory } => {
+                self.generate_documentation(directory)
+            }
+            InterpretedCommand::Exit => self.exit(),
+            InterpretedCommand::AddAlias { alias, for_builtin } => {
+                self.add_alias(alias, for_builtin)
+            }
+            InterpretedCommand::RemoveAlias { alias } => self.remove_alias(alias),
+            InterpretedCommand::ExplainCommand { command } => self.explain_command(&command),
+            InterpretedCommand::AddSynonym { synonym, for_word } => {
+                self.add_synonym(synonym, for_word)
+            }
+            InterpretedCommand::RemoveSynonym { synonym } => self.remove_synonym(synonym),
+            InterpretedCommand::ListSynonyms => self.list_synonyms(),
+            InterpretedCommand::SetShortcut {
+                shortcut,
+                for_builtin,
+            } => self.set_shortcut(shortcut, for_builtin),
+            InterpretedCommand::RemoveShortcut { shortcut } => self.remove_shortcut(shortcut),
+            InterpretedCommand::ListShortcutCollisions => self.list_shortcut_collisions(),
+            InterpretedCommand::SwitchLanguage { language } => self.switch_language(language),
+            InterpretedCommand::SetAppearance { setting, value } => {
+                self.set_appearance(setting, value)
+            }
+            InterpretedCommand::StartStopwatch => self.start_stopwatch(),
+            InterpretedCommand::StopStopwatch => self.stop_stopwatch(),
+            InterpretedCommand::Remind { after, text } => self.remind(after, text),
+            InterpretedCommand::ScheduleJob { command, schedule } => {
+                self.schedule_job(command, schedule)
+            }
+            InterpretedCommand::ListJobs => self.list_jobs(),
+            InterpretedCommand::CancelJob { id } => self.cancel_job(id),
+            InterpretedCommand::Calculate { expression } => self.calculate(expression),
+
+            InterpretedCommand::ShowFile { path } => self.show_file(path)?,
+            InterpretedCommand::ListFiles { folder } => self.list_files(folder)?,
+            InterpretedCommand::CopyFile {
+                from,
+                to,
+                overwrite,
+            } => self.copy_file(from, to, overwrite)?,
+            InterpretedCommand::WhereAmI => self
+                .output
+                .print(format!("You're in {}.", self.cwd.display())),
+            InterpretedCommand::GoToFolder { folder } => self.go_to_folder(folder)?,
+            InterpretedCommand::Confirm { question, action } => {
+                match self.check_before_confirming(&action) {
+                    Ok(()) => self.ask_confirmation(question, *action)?,
+                    Err(reason) => self.report_error(reason),
+                }
+            }
+            InterpretedCommand::RemoveConfigFile => self.remove_config_file()?,
+            InterpretedCommand::UndoLastChange => self.undo_last_change(),
+            InterpretedCommand::RedoLastChange => self.redo_last_change(),
+            InterpretedCommand::ShowChangeLog => self.show_change_log(),
+            InterpretedCommand::StartRecording { path, overwrite } => {
+                self.start_recording(path, overwrite)?
+            }
+            InterpretedCommand::StopRecording => self.stop_recording(),
+            InterpretedCommand::ReplaySession { path } => self.replay_session(path)?,
+            InterpretedCommand::SetConfirmations { enabled } => {
+                self.confirmations = enabled;
+                self.output.print(String::from(if enabled {
+                    "Confirmations are on: Violet asks before destructive commands run."
+                } else {
+                    "Confirmations are off: destructive commands won't ask before they run."
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn report_interpretation_error(&mut self, the_err: InterpretationError) {
+        let message = match the_err {
+            InterpretationError::ArgSpecifierMisused => {
+                self.locale.text(Message::ArgSpecifierMisused).to_owned()
+            }
+            InterpretationError::ArgumentEmpty { argument_name } => self
+                .locale
+                .format(Message::ArgumentEmpty, &[&argument_name]),
+            InterpretationError::ArgumentInvalid { argument, reason } => self
+                .locale
+                .format(Message::ArgumentInvalid, &[&argument, &reason]),
+            InterpretationError::PathNotFound { path } => {
+                self.locale.format(Message::PathNotFound, &[&path])
+            }
+            InterpretationError::FileOperationFailed { path, reason } => self
+                .locale
+                .format(Message::FileOperationFailed, &[&path, &reason]),
+        };
+        self.report_error(message);
+    }
+}
+
+fn file_error(path: &str, the_err: std::io::Error) -> InterpretationError {
+    match the_err.kind() {
+        std::io::ErrorKind::NotFound => InterpretationError::PathNotFound {
+            path: path.to_owned(),
+        },
+        _ => InterpretationError::FileOperationFailed {
+            path: path.to_owned(),
+            reason: the_err.to_string(),
+        },
+    }
+}
+
+#[test]
+fn test_every_builtin_has_consistent_help() {
+    let mut builtins: PathTree<Command> = PathTree::new();
+    let mut output = Output::capturing(OutputMode::Text);
+    let shortcuts = Interpreter::set_all_builtins(&mut builtins, &mut output);
+    assert!(output.take_captured().is_empty());
+    for (path, builtin) in builtins.active_entries() {
+        if TreePath::is_path_a_shortcut(&path) {
+            continue;
+        }
+        let help = builtin.help();
+        assert!(!help.summary.is_empty(), "{} has no summary", path);
+        assert_eq!(
+            TreePath::count_x_nodes_for_path(&path, "<ARG>"),
+            help.arguments.len(),
+            "{} doesn't describe all of its arguments",
+            path
+        );
+        for related in help.related {
+            assert!(
+                builtins.is_node_active(related),
+                "{} refers to a missing command {}",
+                path,
+                related
+            );
+        }
+        assert!(
+            shortcuts.values().any(|for_builtin| *for_builtin == path),
+            "{} has no shortcut",
+            path
+        );
+    }
+}
+
+#[cfg(test)]
+fn create_test_interpreter() -> Interpreter {
+    let mut builtins: PathTree<Command> = PathTree::new();
+    let mut output = Output::capturing(OutputMode::Text);
+    let builtin_shortcuts = Interpreter::set_all_builtins(&mut builtins, &mut output);
+    builtins.set_synonyms(Synonyms::with_defaults());
+    Interpreter {
+        builtin_commands: builtins,
+        builtin_shortcuts,
+        user_config: UserConfig::default(),
+        config_empty_on_boot: true,
+        config_load_failed: false,
+        synonyms_changed: false,
+        locale: Locale::En,
+        localized_paths: BTreeMap::new(),
+        last_status: 0,
+        exiting: false,
+        stopwatch: None,
+        scheduler: Scheduler::new(),
+        last_result: None,
+        cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        pending_confirmation: None,
+        confirmations: true,
+        changes: ChangeLog::default(),
+        recorder: None,
+        state_dir: PathBuf::new(),
+        output,
+    }
+}
+
+#[test]
+fn test_builtins_with_colliding_shortcuts_are_still_registered() {
+    let mut builtins: PathTree<Command> = PathTree::new();
+    let mut output = Output::capturing(OutputMode::Text);
+    let shortcuts = Interpreter::register_builtins(
+        &mut builtins,
+        vec![
+            (Command::from(ListJobsCommand), "list jobs", "[lj]"),
+            (Command::from(CancelJobCommand), "cancel job <ARG>", "[lj]"),
+            (
+                Command::from(ListFilesInCommand),
+                "list files in <ARG>",
+                "[lj]",
+            ),
+        ],
+        &mut output,
+    );
+
+    assert!(builtins.is_node_active("cancel job <ARG>"));
+    assert!(builtins.is_node_active("list files in <ARG>"));
+    assert!(builtins.is_node_active("[lj] <ARG>"));
+    assert_eq!(
+        vec![("[lj]", "list jobs"), ("[lj] <ARG>", "cancel job <ARG>")],
+        shortcuts
+            .iter()
+            .map(|(shortcut, path)| (shortcut.as_str(), path.as_str()))
+            .collect::<Vec<(&str, &str)>>()
+    );
+    assert_eq!(
+        vec!["WARNING: the shortcut [lj] <ARG> is already taken, so builtin [list files in <ARG>] has no shortcut."],
+        output.take_captured()
+    );
+}
+
+#[test]
+fn test_explaining_aliases_shortcuts_and_arguments() {
+    let mut interpreter = create_test_interpreter();
+    interpreter
+        .user_config
+        .aliases
+        .set_by_path(
+            String::from("please say <ARG> and <ARG>"),
+            "repeat <ARG> <ARG>",
+        )
+        .unwrap();
+    interpreter
+        .register_user_shortcut("[t]", "what time is it")
+        .unwrap();
+
+    assert_eq!(
+        Ok((String::from("exit"), vec![])),
+        interpreter.resolve_for_explanation("exit")
+    );
+    assert_eq!(
+        Ok((
+            String::from("what time is it"),
+            vec![String::from("[wtii] is a shortcut for [what time is it].")]
+        )),
+        interpreter.resolve_for_explanation("[wtii]")
+    );
+    assert_eq!(
+        Ok((
+            String::from("what time is it"),
+            vec![String::from("[t] is a shortcut for [what time is it].")]
+        )),
+        interpreter.resolve_for_explanation("[t]")
+    );
+    assert_eq!(
+    Ok((
+        String::from("please say <ARG> and <ARG>"),
+        vec![String::from(
+            "\"please say hi and bye\" runs [please say <ARG> and <ARG>] with the arguments: \"hi\", \"bye\"."
+        )]
+    )),
+    interpreter.resolve_for_explanation("please say hi and bye")
+);
+    assert_eq!(
+        Ok((
+            String::from("please say <ARG> and <ARG>"),
+            vec![
+            String::from("[repeat <ARG> <ARG>] is an alias for [please say <ARG> and <ARG>]."),
+            String::from(
+                "\"repeat x y\" runs [please say <ARG> and <ARG>] with the arguments: \"x\", \"y\"."
+            )
+        ]
+        )),
+        interpreter.resolve_for_explanation("repeat x y")
+    );
+    assert_eq!(
+        Ok((String::from("please say <ARG> and <ARG>"), vec![])),
+        interpreter.resolve_for_explanation("please say <ARG> and <ARG>")
+    );
+    assert!(interpreter.resolve_for_explanation("nothing").is_err());
+}
+
+#[test]
+fn test_every_builtin_has_a_name_in_every_language() {
+    let mut builtins: PathTree<Command> = PathTree::new();
+    Interpreter::set_all_builtins(&mut builtins, &mut Output::capturing(OutputMode::Text));
+    for locale in Locale::all().iter().filter(|locale| **locale != Locale::En) {
+        for (path, _) in builtins.active_entries() {
+            if TreePath::is_path_a_shortcut(&path) {
+                continue;
+            }
+            assert!(
+                locale
+                    .vocabulary()
+                    .iter()
+                    .any(|(english, _)| *english == path),
+                "{} has no name in {}",
+                path,
+                locale.code()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_default_synonyms_stand_for_builtin_words() {
+    let mut builtins: PathTree<Command> = PathTree::new();
+    Interpreter::set_all_builtins(&mut builtins, &mut Output::capturing(OutputMode::Text));
+    for (synonym, word) in Synonyms::with_defaults().iter() {
+        assert!(
+            !builtins.uses_word(synonym),
+            "the default synonym {} is already a builtin word",
+            synonym
+        );
+        assert!(
+            builtins.uses_word(word),
+            "no builtin uses {}, the word for the default synonym {}",
+            word,
+            synonym
+        );
+    }
+}
+
+#[test]
+fn test_switching_vocabularies() {
+    let mut interpreter = create_test_interpreter();
+    assert!(interpreter
+        .builtin_commands
+        .resolve("котра година")
+        .is_err());
+
+    interpreter.apply_vocabulary(Locale::Uk);
+    assert_eq!(Locale::Uk, interpreter.locale);
+    assert_eq!(
+        String::from("котра година"),
+        interpreter
+            .builtin_commands
+            .resolve("Котра година?")
+            .unwrap()
+            .path
+    );
+    assert_eq!(
+        vec![String::from("a"), String::from("b")],
+        interpreter
+            .builtin_commands
+            .resolve("скажи a і b")
+            .unwrap()
+            .args
+    );
+    assert!(interpreter
+        .builtin_commands
+        .is_node_active("what time is it"));
+    assert_eq!(
+        String::from("what time is it"),
+        interpreter.canonical_path("котра година")
+    );
+    assert_eq!(
+        Ok((
+            String::from("what time is it"),
+            vec![String::from(
+                "[котра година] means [what time is it] (українська)."
+            )]
+        )),
+        interpreter.resolve_for_explanation("котра година")
+    );
+
+    interpreter.apply_vocabulary(Locale::En);
+    assert!(interpreter
+        .builtin_commands
+        .resolve("котра година")
+        .is_err());
+    assert!(interpreter.localized_paths.is_empty());
+}
+
+#[test]
+fn test_interpreting_reports_severities() {
+    let mut interpreter = create_test_interpreter();
+    interpreter.interpret(String::from("please say hi and bye"));
+    interpreter.interpret(String::from("remove alias nothing"));
+    interpreter.interpret(String::from("fly me to the moon"));
+    assert_eq!(1, interpreter.last_status);
+    assert_eq!(
+        vec![
+            "Gotcha. Saying hi and bye!",
+            "ERROR: alias nothing does not exist. Can't remove alias which doesn't exist.",
+            "ERROR: fly me to the moon: command does not exist.",
+        ],
+        interpreter.output.take_captured()
+    );
+
+    interpreter.output = Output::capturing(OutputMode::Json);
+    interpreter.interpret(String::from("[psaaa] hi bye"));
+    interpreter.interpret(String::from("fly"));
+    assert_eq!(
+        vec![
+            r#"{"input":"[psaaa] hi bye","command":"please say <ARG> and <ARG>","args":["hi","bye"],"success":true,"messages":[{"severity":"output","text":"Gotcha. Saying hi and bye!"}]}"#,
+            r#"{"input":"fly","command":null,"args":[],"success":false,"messages":[{"severity":"error","text":"fly: command does not exist."}]}"#,
+        ],
+        interpreter.output.take_captured()
+    );
+}
+
+#[test]
+fn test_listing_commands_matching_a_literal_arg_fails() {
+    let mut interpreter = create_test_interpreter();
+    interpreter.interpret(String::from("list available commands matching <ARG>"));
+    assert_eq!(1, interpreter.last_status);
+    assert_eq!(
+        vec![format!(
+            "ERROR: {}",
+            interpreter.locale.text(Message::ArgSpecifierMisused)
+        )],
+        interpreter.output.take_captured()
+    );
+
+    interpreter.interpret(String::from("list available commands matching alias"));
+    assert_eq!(0, interpreter.last_status);
+}
+
+#[test]
+fn test_running_commands_stops_at_the_first_failure() {
+    let mut interpreter = create_test_interpreter();
+    let status = interpreter.run_commands(&[
+        String::from("please say a and b"),
+        String::from("fly"),
+        String::from("please say c and d"),
+    ]);
+    assert_eq!(1, status);
+    assert_eq!(
+        vec![
+            "Gotcha. Saying a and b!",
+            "ERROR: fly: command does not exist."
+        ],
+        interpreter.output.take_captured()
+    );
+
+    assert_eq!(
+        0,
+        interpreter.run_commands(&[String::from("what is your name")])
+    );
+}
+
+#[test]
+fn test_running_piped_input_until_eof() {
+    let interpreter = Mutex::new(create_test_interpreter());
+    let mut script = std::io::Cursor::new("please say a and b\n\n   \nfly");
+    assert_eq!(1, Interpreter::run_input(&interpreter, &mut script, false));
+    let mut interpreter = interpreter.into_inner().unwrap();
+    assert_eq!(
+        vec![
+            "Gotcha. Saying a and b!",
+            "ERROR: fly: command does not exist."
+        ],
+        interpreter.output.take_captured()
+    );
+
+    let interpreter = Mutex::new(interpreter);
+    let mut script = std::io::Cursor::new("what is your name\nexit\nplease say a and b\n");
+    assert_eq!(0, Interpreter::run_input(&interpreter, &mut script, false));
+    assert_eq!(
+        vec!["My name is Violet! Nice to meet you ^_^", "Bye! AYAYA ^_^"],
+        interpreter.lock().unwrap().output.take_captured()
+    );
+}
+
+#[test]
+fn test_stopwatch_and_reminders() {
+    let mut interpreter = create_test_interpreter();
+    interpreter.interpret(String::from("stop stopwatch"));
+    assert_eq!(1, interpreter.last_status);
+    interpreter.interpret(String::from("start stopwatch"));
+    interpreter.interpret(String::from("[ss]"));
+    assert_eq!(1, interpreter.last_status);
+    interpreter.interpret(String::from("stop stopwatch"));
+    assert_eq!(0, interpreter.last_status);
+    let output = interpreter.output.take_captured();
+    assert_eq!(
+        "ERROR: the stopwatch isn't running. Start it with \"start stopwatch\" first!",
+        output[0]
+    );
+    assert!(output[2].starts_with("ERROR: the stopwatch has already been running for 0.0"));
+    assert!(output[3].starts_with("The stopwatch stopped at 0.0"));
+
+    interpreter.interpret(String::from("remind me in forever to blink"));
+    assert_eq!(
+        vec!["ERROR: can't make sense of \"forever\": \"forever\" isn't a number of seconds, minutes, hours or days."],
+        interpreter.output.take_captured()
+    );
+    interpreter.interpret(String::from(
+        "remind me in \"0 seconds\" to \"drink some tea\"",
+    ));
+    let mut delivered = interpreter.output.take_captured();
+    for _ in 0..100 {
+        if delivered.len() > 1 {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        delivered.extend(interpreter.output.take_captured());
+    }
+    assert!(delivered[0].starts_with("I'll remind you to drink some tea at "));
+    assert_eq!("NOTICE: Reminder: drink some tea!", delivered[1]);
+}
+
+#[test]
+fn test_running_and_cancelling_jobs() {
+    let mut interpreter = create_test_interpreter();
+    interpreter.interpret(String::from("list jobs"));
+    interpreter.interpret(String::from("run exit in background"));
+    interpreter.interpret(String::from("[raib] fly"));
+    interpreter.interpret(String::from(
+        "every \"0.5 seconds\" run \"what is your name\"",
+    ));
+    interpreter.interpret(String::from("cancel job 7"));
+    assert_eq!(
+        vec![
+            "No jobs yet! You can start one with \"run <ARG> in background\".",
+            "ERROR: [exit] can't run as a job: jobs can't exit Violet or start other jobs.",
+            "ERROR: fly: command does not exist.",
+            "ERROR: can't make sense of \"0.5 seconds\": jobs can't repeat more often than every second.",
+            "ERROR: job 7 does not exist. Run \"list jobs\" to see your jobs.",
+        ],
+        interpreter.output.take_captured()
+    );
+    interpreter.interpret(String::from("every <ARG> run \"what is your name\""));
+    interpreter.interpret(String::from("at <ARG> run \"what is your name\""));
+    let misused = format!(
+        "ERROR: {}",
+        interpreter.locale.text(Message::ArgSpecifierMisused)
+    );
+    assert_eq!(
+        vec![misused.clone(), misused],
+        interpreter.output.take_captured()
+    );
+
+    let status = interpreter.run_commands(&[
+        String::from("run \"please say a and b\" in background"),
+        String::from("every \"1 hour\" run \"what is your name\""),
+        String::from("at tomorrow run \"what time is it\""),
+        String::from("cancel job 3"),
+        String::from("list jobs"),
+    ]);
+    assert_eq!(0, status);
+    let output = interpreter.output.take_captured();
+    assert_eq!(
+        vec![
+            "Started job 1: run \"please say a and b\" in background.",
+            "NOTICE: job 1 runs \"please say a and b\":",
+            "Gotcha. Saying a and b!",
+        ],
+        output[..3]
+    );
+    assert!(
+        output[3].starts_with("Started job 2: every 1 hour run \"what is your name\", next at ")
+    );
+    assert!(output[4].starts_with("Started job 3: at "));
+    assert!(output[5].starts_with("Cancelled job 3: at "));
+    assert!(output[6].starts_with("Your jobs:\n\n- job 2: every 1 hour run \"what is your name\""));
+    assert_eq!(
+        "WARNING: 1 unfinished job won't run, because Violet is exiting.",
+        output[7]
+    );
+    assert!(interpreter.scheduler.jobs().is_empty());
+}
+
+#[test]
+fn test_calculating_and_reusing_results() {
+    let mut interpreter = create_test_interpreter();
+    interpreter.interpret(String::from("what is three times seven"));
+    interpreter.interpret(String::from("calculate \"ans / 2\""));
+    interpreter.interpret(String::from("what is \"1 + 2\" minus 4?"));
+    interpreter.interpret(String::from("calculate \"2 ^ 64\""));
+    assert_eq!(1, interpreter.last_status);
+    assert_eq!(
+        vec![
+            "three * seven = 21",
+            "ans / 2 = 10.5",
+            "(1 + 2) - 4 = -1",
+            "ERROR: can't make sense of \"2 ^ 64\": the result is too big to calculate.",
+        ],
+        interpreter.output.take_captured()
+    );
+    assert_eq!(Some(Number::Integer(-1)), interpreter.last_result);
+    assert_eq!("-1", interpreter.render_template("{result}"));
+
+    interpreter.output = Output::capturing(OutputMode::Json);
+    interpreter.interpret(String::from("[ca] \"1.5 * 3\""));
+    assert_eq!(
+        vec![
+            r#"{"input":"[ca] \"1.5 * 3\"","command":"calculate <ARG>","args":["1.5 * 3"],"success":true,"result":4.5,"messages":[{"severity":"output","text":"1.5 * 3 = 4.5"}]}"#
+        ],
+        interpreter.output.take_captured()
+    );
+}
+
+#[test]
+fn test_file_commands_and_confirmations() {
+    let folder = std::env::temp_dir().join(format!("violet-files-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&folder);
+    std::fs::create_dir_all(folder.join("backup")).unwrap();
+    std::fs::write(folder.join("notes.txt"), "buy tea\n").unwrap();
+    let folder = folder.canonicalize().unwrap();
+
+    let mut interpreter = create_test_interpreter();
+    interpreter.interpret(format!("go to folder \"{}\"", folder.display()));
+    interpreter.interpret(String::from("list files in ."));
+    interpreter.interpret(String::from("show file notes.txt"));
+    interpreter.interpret(String::from("copy file notes.txt to backup"));
+    interpreter.interpret(String::from("copy file notes.txt to backup/notes.txt"));
+    interpreter.interpret(String::from("no"));
+    interpreter.interpret(String::from("where am i"));
+    assert_eq!(0, interpreter.last_status);
+    interpreter.interpret(String::from("show file missing.txt"));
+    assert_eq!(1, interpreter.last_status);
+    interpreter.interpret(String::from("go to folder notes.txt"));
+    let copy = folder.join("backup").join("notes.txt");
+    assert_eq!(
+        vec![
+            format!("You're in {} now.", folder.display()),
+            format!("Files in {}:\n\n- backup/\n- notes.txt", folder.display()),
+            String::from("buy tea"),
+            format!("Copied notes.txt to {} (8 bytes).", copy.display()),
+            format!("{} already exists. Replace it? (yes/no)", copy.display()),
+            String::from("Okay, nothing has changed."),
+            format!("You're in {}.", folder.display()),
+            String::from("ERROR: missing.txt: no such file or folder."),
+            String::from("ERROR: can't make sense of \"notes.txt\": it's a file, not a folder."),
+        ],
+        interpreter.output.take_captured()
+    );
+    assert_eq!(
+        folder.display().to_string(),
+        interpreter.render_template("{cwd}")
+    );
+
+    std::fs::remove_dir_all(&folder).unwrap();
+}
+
+#[test]
+fn test_confirming_destructive_commands() {
+    let mut interpreter = create_test_interpreter();
+    interpreter.interpret(String::from("add alias bye for builtin exit"));
+    interpreter.output.take_captured();
+    interpreter.interpret(String::from("remove alias nothing"));
+    assert_eq!(1, interpreter.last_status);
+    interpreter.interpret(String::from("remove alias bye"));
+    interpreter.interpret(String::from("nope"));
+    interpreter.interpret(String::from("remove alias bye"));
+    interpreter.interpret(String::from("yes"));
+    assert_eq!(
+        vec![
+            "ERROR: alias nothing does not exist. Can't remove alias which doesn't exist.",
+            "Remove alias bye? (yes/no)",
+            "Okay, nothing has changed. Running your new command instead.",
+            "ERROR: nope: command does not exist.",
+            "Remove alias bye? (yes/no)",
+            "Removed alias bye.",
+        ],
+        interpreter.output.take_captured()
+    );
+    assert!(interpreter.user_config.aliases.is_empty());
+
+    interpreter.interpret(String::from("add alias bye for builtin exit"));
+    interpreter.interpret(String::from("remove alias bye"));
+    interpreter.drop_unanswered_confirmation();
+    assert!(!interpreter.user_config.aliases.is_empty());
+    interpreter.set_confirmations(false);
+    interpreter.interpret(String::from("remove alias bye"));
+    assert_eq!(
+        vec![
+            "Remove alias bye? (yes/no)",
+            "WARNING: the last question wasn't answered, so nothing has changed. Run Violet with --yes to answer yes to every question.",
+            "INFO: Remove alias bye? Yes, confirmations are off.",
+            "Removed alias bye.",
+        ],
+        interpreter.output.take_captured()
+    );
+
+    interpreter.set_confirmations(true);
+    interpreter.interpret(String::from("add alias bye for builtin exit"));
+    interpreter.output.take_captured();
+    interpreter.interpret(String::from("remove alias bye"));
+    interpreter.interpret(String::from("please say Hello and World!"));
+    assert_eq!(
+        vec![
+            "Remove alias bye? (yes/no)",
+            "Okay, nothing has changed. Running your new command instead.",
+            "Gotcha. Saying Hello and World!!",
+        ],
+        interpreter.output.take_captured()
+    );
+    assert!(!interpreter.user_config.aliases.is_empty());
+}
+
+#[test]
+fn test_undoing_and_redoing_changes() {
+    let mut interpreter = create_test_interpreter();
+    interpreter.set_confirmations(false);
+    interpreter.interpret(String::from("undo last change"));
+    interpreter.interpret(String::from("show change log"));
+    interpreter.interpret(String::from("add alias bye for builtin exit"));
+    interpreter.interpret(String::from("add synonym leave for word exit"));
+    interpreter.interpret(String::from("set shortcut [bb] for exit"));
+    assert_eq!(
+        vec![
+            "ERROR: there's nothing to undo, no aliases, shortcuts or synonyms have changed in this session",
+            "No changes yet! Adding or removing aliases, shortcuts and synonyms shows up here.",
+        ],
+        interpreter.output.take_captured()
+    );
+    assert_eq!(3, interpreter.changes.done().len());
+
+    interpreter.interpret(String::from("undo last change"));
+    interpreter.interpret(String::from("undo last change"));
+    assert!(!interpreter.builtin_commands.synonyms().contains("leave"));
+    assert!(interpreter.user_config.shortcuts.is_empty());
+    interpreter.interpret(String::from("redo last change"));
+    assert!(interpreter.builtin_commands.synonyms().contains("leave"));
+    interpreter.interpret(String::from("show change log"));
+    interpreter.interpret(String::from("remove alias bye"));
+    interpreter.interpret(String::from("redo last change"));
+    interpreter.interpret(String::from("undo last change"));
+    assert!(interpreter.user_config.aliases.is_node_active("bye"));
+    assert_eq!(
+        vec![
+            "Undid the last change: set shortcut [bb] for [exit].",
+            "Undid the last change: added synonym [leave] for [exit].",
+            "Redid the change: added synonym [leave] for [exit].",
+            "Changes in this session, the latest first:\n\n- added synonym [leave] for [exit];\n- added alias [bye] for [exit];\n\nUndone changes, the next one to redo first:\n\n- set shortcut [bb] for [exit];",
+            "INFO: Remove alias bye? Yes, confirmations are off.",
+            "Removed alias bye.",
+            "ERROR: there's nothing to redo, undo a change first",
+            "Undid the last change: removed alias [bye] for [exit].",
+        ],
+        interpreter.output.take_captured()
+    );
+}
+
+#[test]
+fn test_recording_and_replaying_a_session() {
+    let folder = std::env::temp_dir().join(format!("violet-recording-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&folder);
+    std::fs::create_dir_all(&folder).unwrap();
+    let folder = folder.canonicalize().unwrap();
+    let transcript_path = folder.join("session.jsonl");
+
+    let mut interpreter = create_test_interpreter();
+    interpreter.set_confirmations(false);
+    interpreter.interpret(format!("go to folder \"{}\"", folder.display()));
+    interpreter.interpret(String::from("stop recording"));
+    interpreter.interpret(String::from("start recording to session.jsonl"));
+    interpreter.interpret(String::from("add alias bye for builtin exit"));
+    interpreter.interpret(String::from("please say a and b"));
+    interpreter.interpret(String::from("nope"));
+    interpreter.interpret(String::from("remove alias bye"));
+    interpreter.interpret(String::from("stop recording"));
+    interpreter.output.take_captured();
+
+    let transcript = std::fs::read_to_string(&transcript_path).unwrap();
+    let entries = transcript::parse_transcript(&transcript).unwrap();
+    assert_eq!(4, entries.len());
+    assert_eq!(
+        Some(String::from("please say <ARG> and <ARG>")),
+        entries[1].record.command
+    );
+    assert_eq!(vec!["a", "b"], entries[1].record.args);
+    assert!(!entries[2].record.success);
+
+    interpreter.interpret(String::from("replay session session.jsonl"));
+    assert_eq!(0, interpreter.last_status);
+    std::fs::write(
+        &transcript_path,
+        transcript.replace("Saying a and b", "Saying A and B"),
+    )
+    .unwrap();
+    interpreter.interpret(String::from("replay session session.jsonl"));
+    assert_eq!(1, interpreter.last_status);
+    assert!(interpreter.user_config.aliases.is_empty());
+    assert_eq!(
+        vec![
+            String::from("Replayed 4 command(s) from session.jsonl, and the output matches the recording."),
+            String::from("Command 2, \"please say a and b\", differs:\n- Gotcha. Saying A and B!\n+ Gotcha. Saying a and b!"),
+            String::from("ERROR: the replay differs from the recording in 1 of 4 commands"),
+        ],
+        interpreter.output.take_captured()
+    );
+    let _ = std::fs::remove_dir_all(&folder);
+}
+
+#[test]
+fn test_unanswered_confirmations_fail_the_run() {
+    let folder = std::env::temp_dir().join(format!("violet-unanswered-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&folder);
+    std::fs::create_dir_all(&folder).unwrap();
+
+    let mut interpreter = create_test_interpreter();
+    interpreter.state_dir = folder.clone();
+    let status = interpreter.run_commands(&[
+        String::from("add alias bye for builtin exit"),
+        String::from("remove alias bye"),
+    ]);
+    assert_eq!(1, status);
+    assert!(interpreter.user_config.aliases.is_node_active("bye"));
+
+    let mut interpreter = create_test_interpreter();
+    interpreter.state_dir = folder.clone();
+    let interpreter = Mutex::new(interpreter);
+    let mut script = std::io::Cursor::new("add alias bye for builtin exit\nremove alias bye\n");
+    assert_eq!(1, Interpreter::run_input(&interpreter, &mut script, false));
+    assert_eq!(
+        Some(&String::from(
+            "WARNING: the last question wasn't answered, so nothing has changed. Run Violet with --yes to answer yes to every question."
+        )),
+        interpreter
+            .lock()
+            .unwrap()
+            .output
+            .take_captured()
+            .get(1)
+    );
+    let _ = std::fs::remove_dir_all(&folder);
+}
+
+#[test]
+fn test_config_that_failed_to_load_is_not_overwritten() {
+    let folder = std::env::temp_dir().join(format!("violet-corrupted-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&folder);
+    std::fs::create_dir_all(&folder).unwrap();
+    let config_name = folder.join(config::get_config_file_name());
+    std::fs::write(&config_name, "{ not json").unwrap();
+
+    let mut interpreter = create_test_interpreter();
+    interpreter.state_dir = folder.clone();
+    interpreter.config_load_failed = true;
+    interpreter.run_line(String::from("add alias bye for builtin exit"));
+    interpreter.output.take_captured();
+    interpreter.save_state();
+    assert_eq!("{ not json", std::fs::read_to_string(&config_name).unwrap());
+    assert_eq!(1, interpreter.output.take_captured().len());
+
+    interpreter.run_line(String::from("remove config file"));
+    interpreter.run_line(String::from("yes"));
+    interpreter.save_state();
+    assert!(UserConfig::from_json(&std::fs::read_to_string(&config_name).unwrap()).is_ok());
+    let _ = std::fs::remove_dir_all(&folder);
 }
@@ -0,0 +1,167 @@
+use super::*;
+use crate::io::transcript;
+
+use std::collections::BTreeSet;
+
+const UPDATE_VARIABLE: &str = "VIOLET_UPDATE_GOLDEN";
+const SCRIPT_EXTENSION: &str = "script";
+const GOLDEN_EXTENSION: &str = "golden";
+const VARYING_PREFIX: &str = "~ ";
+const FOLDER_PLACEHOLDER: &str = "<TMP>";
+
+struct Session {
+    transcript: String,
+    commands: BTreeSet<String>,
+}
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+}
+
+fn prepare_folder(name: &str) -> PathBuf {
+    let folder =
+        std::env::temp_dir().join(format!("violet-golden-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&folder);
+    std::fs::create_dir_all(folder.join("backup")).unwrap();
+    std::fs::write(folder.join("notes.txt"), "buy tea\nwrite tests\n").unwrap();
+
+    folder.canonicalize().unwrap()
+}
+
+fn run_script(script: &str, folder: &Path) -> Session {
+    let mut interpreter = create_test_interpreter();
+    interpreter.cwd = folder.to_path_buf();
+    interpreter.state_dir = folder.to_path_buf();
+    let folder_name = folder.display().to_string();
+
+    let mut transcript: Vec<String> = vec![];
+    let mut commands = BTreeSet::new();
+    for line in script.lines() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (input, varying) = match line.strip_prefix(VARYING_PREFIX) {
+            Some(input) => (input, true),
+            None => (line, false),
+        };
+
+        transcript.push(format!("<<VIO>> {}", input));
+        let record = interpreter
+            .interpret_line(input.to_owned())
+            .unwrap_or_default();
+        if let Some(command) = record.command {
+            commands.insert(command);
+        }
+        interpreter.run_due_jobs();
+        let output = interpreter.output.take_captured();
+        match (varying, record.success) {
+            (true, true) => transcript.push(String::from("(output varies)")),
+            (true, false) => transcript.push(String::from("(output varies, the command failed)")),
+            (false, _) => transcript.extend(
+                output
+                    .iter()
+                    .map(|text| text.replace(&folder_name, FOLDER_PLACEHOLDER)),
+            ),
+        }
+        if interpreter.exiting {
+            break;
+        }
+    }
+
+    interpreter.drop_unanswered_confirmation();
+    interpreter.abandon_jobs();
+    let leftovers = interpreter.output.take_captured();
+    if !leftovers.is_empty() {
+        transcript.push(String::from("<<END>>"));
+        transcript.extend(leftovers);
+    }
+
+    Session {
+        transcript: transcript.join("\n") + "\n",
+        commands,
+    }
+}
+
+fn find_scripts() -> Vec<PathBuf> {
+    let mut scripts: Vec<PathBuf> = std::fs::read_dir(golden_dir())
+        .unwrap()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some(SCRIPT_EXTENSION))
+        .collect();
+    scripts.sort();
+
+    scripts
+}
+
+#[test]
+fn test_golden_sessions() {
+    let updating = std::env::var_os(UPDATE_VARIABLE).is_some();
+    let mut failures: Vec<String> = vec![];
+    let mut covered = BTreeSet::new();
+    for script_path in find_scripts() {
+        let name = script_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap()
+            .to_owned();
+        let folder = prepare_folder(&name);
+        let session = run_script(&std::fs::read_to_string(&script_path).unwrap(), &folder);
+        let _ = std::fs::remove_dir_all(&folder);
+        covered.extend(session.commands);
+
+        let golden_path = script_path.with_extension(GOLDEN_EXTENSION);
+        if updating {
+            std::fs::write(&golden_path, &session.transcript).unwrap();
+            continue;
+        }
+        let expected = match std::fs::read_to_string(&golden_path) {
+            Ok(expected) => expected,
+            Err(_) => {
+                failures.push(format!(
+                    "{} has no golden file, run the tests with {}=1 to create it.",
+                    name, UPDATE_VARIABLE
+                ));
+                continue;
+            }
+        };
+        let diff = transcript::diff_lines(
+            &expected.lines().map(str::to_owned).collect::<Vec<String>>(),
+            &session
+                .transcript
+                .lines()
+                .map(str::to_owned)
+                .collect::<Vec<String>>(),
+        );
+        if !diff.is_empty() {
+            failures.push(format!(
+                "{} differs from {}:\n{}",
+                name,
+                golden_path.display(),
+                diff.join("\n")
+            ));
+        }
+    }
+
+    let mut builtins: PathTree<Command> = PathTree::new();
+    Interpreter::set_all_builtins(&mut builtins, &mut Output::capturing(OutputMode::Text));
+    let uncovered: Vec<String> = builtins
+        .active_entries()
+        .map(|(path, _)| path)
+        .filter(|path| !TreePath::is_path_a_shortcut(path) && !covered.contains(path))
+        .collect();
+    if !uncovered.is_empty() {
+        failures.push(format!(
+            "no golden session runs these commands: {}",
+            uncovered.join(", ")
+        ));
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{}\n\nIf the new output is right, run the tests with {}=1 to update the golden files.",
+        failures.join("\n\n"),
+        UPDATE_VARIABLE
+    );
+}
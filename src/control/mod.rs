@@ -1,2 +1,4 @@
+pub mod changes;
 pub mod commands;
 pub mod interpreter;
+pub mod scheduler;
@@ -0,0 +1,236 @@
+use crate::util::time;
+
+use chrono::{DateTime, Duration, Local};
+
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JobSchedule {
+    Now,
+    Every(Duration),
+    At(DateTime<Local>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Job {
+    pub id: usize,
+    pub command: String,
+    pub schedule: JobSchedule,
+    pub next_run: DateTime<Local>,
+}
+
+impl Job {
+    pub fn describe(&self) -> String {
+        match self.schedule {
+            JobSchedule::Now => format!("run \"{}\" in background", self.command),
+            JobSchedule::Every(interval) => format!(
+                "every {} run \"{}\", next at {}",
+                time::format_duration(interval),
+                self.command,
+                self.next_run.format("%I:%M:%S %p")
+            ),
+            JobSchedule::At(moment) => format!(
+                "at {} run \"{}\"",
+                moment.format("%Y-%m-%d %I:%M:%S %p"),
+                self.command
+            ),
+        }
+    }
+}
+
+#[derive(Default)]
+struct JobList {
+    jobs: Vec<Job>,
+    last_id: usize,
+}
+
+#[derive(Default)]
+pub struct Scheduler {
+    jobs: Mutex<JobList>,
+    changed: Condvar,
+}
+
+impl Scheduler {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Scheduler::default())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, JobList> {
+        self.jobs
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn add(&self, command: String, schedule: JobSchedule, now: DateTime<Local>) -> Job {
+        let next_run = match schedule {
+            JobSchedule::Now => now,
+            JobSchedule::Every(interval) => now + interval,
+            JobSchedule::At(moment) => moment,
+        };
+        let mut list = self.lock();
+        list.last_id += 1;
+        let job = Job {
+            id: list.last_id,
+            command,
+            schedule,
+            next_run,
+        };
+        list.jobs.push(job.clone());
+        self.changed.notify_all();
+
+        job
+    }
+
+    pub fn cancel(&self, id: usize) -> Option<Job> {
+        let mut list = self.lock();
+        let index = list.jobs.iter().position(|job| job.id == id)?;
+        let job = list.jobs.remove(index);
+        self.changed.notify_all();
+
+        Some(job)
+    }
+
+    pub fn clear(&self) -> Vec<Job> {
+        let jobs = std::mem::take(&mut self.lock().jobs);
+        self.changed.notify_all();
+
+        jobs
+    }
+
+    pub fn jobs(&self) -> Vec<Job> {
+        self.lock().jobs.clone()
+    }
+
+    fn take_due(list: &mut JobList, now: DateTime<Local>) -> Vec<Job> {
+        let mut due: Vec<Job> = vec![];
+        list.jobs.retain_mut(|job| {
+            if job.next_run > now {
+                return true;
+            }
+            due.push(job.clone());
+            match job.schedule {
+                JobSchedule::Every(interval) => {
+                    while job.next_run <= now {
+                        job.next_run += interval;
+                    }
+                    true
+                }
+                _ => false,
+            }
+        });
+
+        due
+    }
+
+    pub fn due_jobs(&self, now: DateTime<Local>) -> Vec<Job> {
+        Scheduler::take_due(&mut self.lock(), now)
+    }
+
+    fn wait_for_due_jobs(&self) -> Vec<Job> {
+        let mut list = self.lock();
+        loop {
+            let now = Local::now();
+            let due = Scheduler::take_due(&mut list, now);
+            if !due.is_empty() {
+                return due;
+            }
+
+            let next_run = list.jobs.iter().map(|job| job.next_run).min();
+            list = match next_run.and_then(|next_run| (next_run - now).to_std().ok()) {
+                Some(timeout) => {
+                    self.changed
+                        .wait_timeout(list, timeout)
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .0
+                }
+                None => self
+                    .changed
+                    .wait(list)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()),
+            };
+        }
+    }
+
+    pub fn spawn<F>(scheduler: Arc<Scheduler>, mut run: F) -> JoinHandle<()>
+    where
+        F: FnMut(Job) + Send + 'static,
+    {
+        std::thread::spawn(move || loop {
+            for job in scheduler.wait_for_due_jobs() {
+                run(job);
+            }
+        })
+    }
+}
+
+#[test]
+fn test_taking_due_jobs() {
+    let scheduler = Scheduler::new();
+    let now = Local::now();
+    let background = scheduler.add(String::from("what time is it"), JobSchedule::Now, now);
+    let every = scheduler.add(
+        String::from("what is the date"),
+        JobSchedule::Every(Duration::minutes(5)),
+        now,
+    );
+    scheduler.add(
+        String::from("exit"),
+        JobSchedule::At(now + Duration::hours(1)),
+        now,
+    );
+    assert_eq!((1, 2), (background.id, every.id));
+
+    assert_eq!(vec![background], scheduler.due_jobs(now));
+    assert!(scheduler.due_jobs(now).is_empty());
+
+    let due = scheduler.due_jobs(now + Duration::minutes(11));
+    assert_eq!(
+        vec![2],
+        due.iter().map(|job| job.id).collect::<Vec<usize>>()
+    );
+    assert_eq!(
+        now + Duration::minutes(15),
+        scheduler.jobs()[0].next_run,
+        "a late repeating job runs once and then keeps its rhythm"
+    );
+
+    assert_eq!(
+        vec![2, 3],
+        scheduler
+            .due_jobs(now + Duration::hours(2))
+            .iter()
+            .map(|job| job.id)
+            .collect::<Vec<usize>>()
+    );
+    assert_eq!(1, scheduler.jobs().len());
+}
+
+#[test]
+fn test_cancelling_jobs() {
+    let scheduler = Scheduler::new();
+    let now = Local::now();
+    let job = scheduler.add(
+        String::from("what time is it"),
+        JobSchedule::Every(Duration::seconds(1)),
+        now,
+    );
+    assert_eq!(Some(job), scheduler.cancel(1));
+    assert_eq!(None, scheduler.cancel(1));
+    assert!(scheduler.due_jobs(now + Duration::hours(1)).is_empty());
+}
+
+#[test]
+fn test_running_jobs_on_the_scheduler_thread() {
+    let scheduler = Scheduler::new();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    Scheduler::spawn(Arc::clone(&scheduler), move |job| {
+        sender.send(job.command).unwrap();
+    });
+
+    scheduler.add(String::from("say hi"), JobSchedule::Now, Local::now());
+    assert_eq!(
+        Ok(String::from("say hi")),
+        receiver.recv_timeout(std::time::Duration::from_secs(5))
+    );
+}
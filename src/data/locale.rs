@@ -0,0 +1,255 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Uk,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Message {
+    Welcome,
+    Version,
+    CreatedBy,
+    HelpHint,
+    CommandDoesNotExist,
+    Goodbye,
+    ArgSpecifierMisused,
+    ArgumentEmpty,
+    ArgumentInvalid,
+    PathNotFound,
+    FileOperationFailed,
+    LanguageSwitched,
+    LanguageUnknown,
+}
+
+const ALL_LOCALES: &[Locale] = &[Locale::En, Locale::Uk];
+
+const UKRAINIAN_VOCABULARY: &[(&str, &str)] = &[
+    ("exit", "вихід"),
+    ("what time is it", "котра година"),
+    ("what is your name", "як тебе звати"),
+    ("please say <ARG> and <ARG>", "скажи <ARG> і <ARG>"),
+    (
+        "add alias <ARG> for builtin <ARG>",
+        "додай псевдонім <ARG> для команди <ARG>",
+    ),
+    ("remove alias <ARG>", "видали псевдонім <ARG>"),
+    ("list aliases", "покажи псевдоніми"),
+    ("help", "допомога"),
+    ("help topic <ARG>", "допомога з теми <ARG>"),
+    ("list available commands", "покажи доступні команди"),
+    (
+        "list available commands matching <ARG>",
+        "покажи доступні команди зі словом <ARG>",
+    ),
+    ("explain command <ARG>", "поясни команду <ARG>"),
+    (
+        "add synonym <ARG> for word <ARG>",
+        "додай синонім <ARG> для слова <ARG>",
+    ),
+    ("remove synonym <ARG>", "видали синонім <ARG>"),
+    ("list synonyms", "покажи синоніми"),
+    (
+        "set shortcut <ARG> for <ARG>",
+        "признач скорочення <ARG> для <ARG>",
+    ),
+    ("remove shortcut <ARG>", "видали скорочення <ARG>"),
+    ("list shortcut collisions", "покажи збіги скорочень"),
+    (
+        "generate documentation to <ARG>",
+        "створи документацію в <ARG>",
+    ),
+    ("switch language to <ARG>", "зміни мову на <ARG>"),
+    ("set prompt to <ARG>", "встанови запрошення <ARG>"),
+    ("set banner to <ARG>", "встанови привітання <ARG>"),
+    ("set exit message to <ARG>", "встанови прощання <ARG>"),
+    ("set theme to <ARG>", "встанови тему <ARG>"),
+    ("what time is it in <ARG>", "котра година в <ARG>"),
+    ("what is the date", "яка сьогодні дата"),
+    ("start stopwatch", "запусти секундомір"),
+    ("stop stopwatch", "зупини секундомір"),
+    (
+        "remind me in <ARG> to <ARG>",
+        "нагадай через <ARG> щоб <ARG>",
+    ),
+    ("how long until <ARG>", "скільки лишилось до <ARG>"),
+    ("run <ARG> in background", "виконай <ARG> у фоні"),
+    ("every <ARG> run <ARG>", "кожні <ARG> виконуй <ARG>"),
+    ("at <ARG> run <ARG>", "о <ARG> виконай <ARG>"),
+    ("list jobs", "покажи завдання"),
+    ("cancel job <ARG>", "скасуй завдання <ARG>"),
+    ("calculate <ARG>", "обчисли <ARG>"),
+    ("what is <ARG> plus <ARG>", "скільки буде <ARG> плюс <ARG>"),
+    (
+        "what is <ARG> minus <ARG>",
+        "скільки буде <ARG> мінус <ARG>",
+    ),
+    (
+        "what is <ARG> times <ARG>",
+        "скільки буде <ARG> помножити на <ARG>",
+    ),
+    (
+        "what is <ARG> divided by <ARG>",
+        "скільки буде <ARG> поділити на <ARG>",
+    ),
+    ("show file <ARG>", "покажи файл <ARG>"),
+    ("list files in <ARG>", "покажи файли в <ARG>"),
+    ("copy file <ARG> to <ARG>", "скопіюй файл <ARG> у <ARG>"),
+    ("where am i", "де я"),
+    ("go to folder <ARG>", "перейди до теки <ARG>"),
+    ("remove config file", "видали файл налаштувань"),
+    ("set confirmations to <ARG>", "встанови підтвердження <ARG>"),
+    ("undo last change", "скасуй останню зміну"),
+    ("redo last change", "поверни останню зміну"),
+    ("show change log", "покажи журнал змін"),
+    ("start recording to <ARG>", "почни запис у <ARG>"),
+    ("stop recording", "зупини запис"),
+    ("replay session <ARG>", "відтвори сеанс <ARG>"),
+];
+
+impl Locale {
+    pub fn all() -> &'static [Locale] {
+        ALL_LOCALES
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Uk => "uk",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Uk => "українська",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Locale> {
+        match name.trim().to_lowercase().as_str() {
+            "en" | "english" | "англійська" => Some(Locale::En),
+            "uk" | "ua" | "ukrainian" | "українська" => Some(Locale::Uk),
+            _ => None,
+        }
+    }
+
+    pub fn from_env_value(value: &str) -> Option<Locale> {
+        let language = value.split(['_', '.', '@', '-']).next()?;
+        match language.to_lowercase().as_str() {
+            "en" | "c" | "posix" => Some(Locale::En),
+            "uk" => Some(Locale::Uk),
+            _ => None,
+        }
+    }
+
+    pub fn from_env() -> Locale {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|variable| std::env::var(variable).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Locale::from_env_value(&value))
+            .unwrap_or_default()
+    }
+
+    pub fn vocabulary(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => &[],
+            Locale::Uk => UKRAINIAN_VOCABULARY,
+        }
+    }
+
+    pub fn text(&self, message: Message) -> &'static str {
+        match (self, message) {
+            (Locale::Uk, Message::Welcome) => "Вітаю! Я {}, інтерпретатор команд!",
+            (Locale::Uk, Message::Version) => "Версія {}: {};",
+            (Locale::Uk, Message::CreatedBy) => "Автор: {}.",
+            (Locale::Uk, Message::HelpHint) => "Щоб дізнатися основи роботи з Violet, введіть: \nдопомога\n\tабо\n[h]\n і натисніть <ENTER>.",
+            (Locale::Uk, Message::CommandDoesNotExist) => "{}: такої команди не існує.",
+            (Locale::Uk, Message::Goodbye) => "Бувай! AYAYA ^_^",
+            (Locale::Uk, Message::ArgSpecifierMisused) => "специфікатор <ARG> передано команді як аргумент!\n\nNOTE: будь ласка, вкажіть справжній аргумент.\nНаприклад, замість\n<<VIO>> поясни команду <ARG>\n  напишіть\n<<VIO>> поясни команду допомога\n",
+            (Locale::Uk, Message::ArgumentEmpty) => "аргумент [{}] порожній, а тут так не можна!",
+            (Locale::Uk, Message::ArgumentInvalid) => "не можу зрозуміти \"{}\": {}.",
+            (Locale::Uk, Message::PathNotFound) => "{}: такого файлу чи теки не існує.",
+            (Locale::Uk, Message::FileOperationFailed) => "не вдалося працювати з {}: {}.",
+            (Locale::Uk, Message::LanguageSwitched) => "тепер я розмовляю мовою: {}.",
+            (Locale::Uk, Message::LanguageUnknown) => "я ще не знаю мови \"{}\". Я знаю: {}.",
+            (_, Message::Welcome) => "Welcome to {} the command interpreter!",
+            (_, Message::Version) => "{}'s version is {};",
+            (_, Message::CreatedBy) => "Created by {}.",
+            (_, Message::HelpHint) => "To get help with the basics of Violet, type: \nhelp\n\tor\n[h]\n and press <ENTER>.",
+            (_, Message::CommandDoesNotExist) => "{}: command does not exist.",
+            (_, Message::Goodbye) => "Bye! AYAYA ^_^",
+            (_, Message::ArgSpecifierMisused) => "<ARG> specifier used in a command directly as an argument!\n\nNOTE: please specify an actual argument instead.\nPassing <ARG> as a single self-contained argument without quotation marks (like this: please say <ARG> and <ARG>) to a command is considered a mistake on the user's side.\nExample: instead of\n<<VIO>> explain command <ARG>\n  please use\n<<VIO>> explain command help\n",
+            (_, Message::ArgumentEmpty) => "Argument named [{}] is empty, which is not allowed in this context!",
+            (_, Message::ArgumentInvalid) => "can't make sense of \"{}\": {}.",
+            (_, Message::PathNotFound) => "{}: no such file or folder.",
+            (_, Message::FileOperationFailed) => "couldn't work with {}: {}.",
+            (_, Message::LanguageSwitched) => "switched the language to {}.",
+            (_, Message::LanguageUnknown) => "Violet doesn't speak \"{}\" yet. The languages are: {}.",
+        }
+    }
+
+    pub fn format(&self, message: Message, args: &[&str]) -> String {
+        let mut args = args.iter();
+        let parts: Vec<&str> = self.text(message).split("{}").collect();
+        let mut formatted = String::from(parts[0]);
+        for part in &parts[1..] {
+            formatted.push_str(args.next().copied().unwrap_or_default());
+            formatted.push_str(part);
+        }
+
+        formatted
+    }
+}
+
+#[test]
+fn test_picking_locale_from_environment_values() {
+    assert_eq!(Some(Locale::Uk), Locale::from_env_value("uk_UA.UTF-8"));
+    assert_eq!(Some(Locale::En), Locale::from_env_value("en_US.UTF-8"));
+    assert_eq!(Some(Locale::En), Locale::from_env_value("C"));
+    assert_eq!(None, Locale::from_env_value("ja_JP.UTF-8"));
+    assert_eq!(None, Locale::from_env_value(""));
+}
+
+#[test]
+fn test_picking_locale_by_name() {
+    assert_eq!(Some(Locale::Uk), Locale::from_name(" Українська "));
+    assert_eq!(Some(Locale::Uk), Locale::from_name("UK"));
+    assert_eq!(Some(Locale::En), Locale::from_name("english"));
+    assert_eq!(None, Locale::from_name("klingon"));
+}
+
+#[test]
+fn test_formatting_messages() {
+    assert_eq!(
+        "blah: command does not exist.",
+        Locale::En.format(Message::CommandDoesNotExist, &["blah"])
+    );
+    assert_eq!(
+        "Версія VIOLET: 0.2.0;",
+        Locale::Uk.format(Message::Version, &["VIOLET", "0.2.0"])
+    );
+    assert_eq!(
+        "Bye! AYAYA ^_^",
+        Locale::En.format(Message::Goodbye, &["unused"])
+    );
+}
+
+#[test]
+fn test_vocabularies_keep_the_arguments() {
+    for locale in Locale::all() {
+        for (path, localized) in locale.vocabulary() {
+            assert_eq!(
+                path.matches("<ARG>").count(),
+                localized.matches("<ARG>").count(),
+                "{} in {} has a different number of arguments",
+                localized,
+                locale.code()
+            );
+        }
+    }
+}
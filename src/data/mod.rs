@@ -1 +1,5 @@
+pub mod locale;
+pub mod normalization;
 pub mod pathtree;
+pub mod synonyms;
+pub mod userconfig;
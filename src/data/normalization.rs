@@ -0,0 +1,197 @@
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', '!', '?', ';', ':'];
+const DEFAULT_FILLER_WORDS: [&str; 2] = ["please", "the"];
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Normalization {
+    pub fold_case: bool,
+    pub strip_punctuation: bool,
+    pub fillers: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Leniency {
+    pub folded_case: bool,
+    pub stripped_punctuation: bool,
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Self {
+            fold_case: true,
+            strip_punctuation: true,
+            fillers: DEFAULT_FILLER_WORDS
+                .iter()
+                .map(|filler| filler.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl Normalization {
+    pub fn strict() -> Self {
+        Self {
+            fold_case: false,
+            strip_punctuation: false,
+            fillers: vec![],
+        }
+    }
+
+    pub fn without_fillers() -> Self {
+        Self {
+            fillers: vec![],
+            ..Self::default()
+        }
+    }
+
+    pub fn is_strict(&self) -> bool {
+        *self == Normalization::strict()
+    }
+
+    pub fn union(&self, other: &Normalization) -> Normalization {
+        let mut fillers = self.fillers.clone();
+        for filler in &other.fillers {
+            if !fillers.contains(filler) {
+                fillers.push(filler.clone());
+            }
+        }
+
+        Normalization {
+            fold_case: self.fold_case || other.fold_case,
+            strip_punctuation: self.strip_punctuation || other.strip_punctuation,
+            fillers,
+        }
+    }
+
+    pub fn allows(&self, leniency: Leniency, skipped_fillers: &[String]) -> bool {
+        (!leniency.folded_case || self.fold_case)
+            && (!leniency.stripped_punctuation || self.strip_punctuation)
+            && skipped_fillers
+                .iter()
+                .all(|skipped| self.is_filler(skipped))
+    }
+
+    pub fn is_filler(&self, word: &str) -> bool {
+        let word = if self.strip_punctuation {
+            Normalization::strip_trailing_punctuation(word)
+        } else {
+            word
+        };
+        self.fillers.iter().any(|filler| {
+            filler == word || (self.fold_case && filler.to_lowercase() == word.to_lowercase())
+        })
+    }
+
+    pub fn compare(&self, input_word: &str, path_node: &str) -> Option<Leniency> {
+        if input_word == path_node {
+            return Some(Leniency::default());
+        }
+
+        let strip = |word| match self.strip_punctuation {
+            true => Normalization::strip_trailing_punctuation(word),
+            false => word,
+        };
+        let (input_stripped, node_stripped) = (strip(input_word), strip(path_node));
+        let stripped_punctuation = input_stripped != input_word || node_stripped != path_node;
+        if input_stripped == node_stripped {
+            return Some(Leniency {
+                folded_case: false,
+                stripped_punctuation,
+            });
+        }
+        if self.fold_case && input_stripped.to_lowercase() == node_stripped.to_lowercase() {
+            return Some(Leniency {
+                folded_case: true,
+                stripped_punctuation,
+            });
+        }
+
+        None
+    }
+
+    pub fn match_key(word: &str) -> String {
+        Normalization::strip_trailing_punctuation(word).to_lowercase()
+    }
+
+    pub fn strip_trailing_punctuation(word: &str) -> &str {
+        let stripped = word.trim_end_matches(TRAILING_PUNCTUATION);
+        if stripped.is_empty() {
+            word
+        } else {
+            stripped
+        }
+    }
+}
+
+impl Leniency {
+    pub fn combine(self, other: Leniency) -> Leniency {
+        Leniency {
+            folded_case: self.folded_case || other.folded_case,
+            stripped_punctuation: self.stripped_punctuation || other.stripped_punctuation,
+        }
+    }
+}
+
+#[test]
+fn test_comparing_words_reports_leniency() {
+    let natural = Normalization::default();
+
+    assert_eq!(Some(Leniency::default()), natural.compare("time", "time"));
+    assert_eq!(
+        Some(Leniency {
+            folded_case: true,
+            stripped_punctuation: false
+        }),
+        natural.compare("What", "what")
+    );
+    assert_eq!(
+        Some(Leniency {
+            folded_case: false,
+            stripped_punctuation: true
+        }),
+        natural.compare("it?!", "it")
+    );
+    assert_eq!(
+        Some(Leniency {
+            folded_case: true,
+            stripped_punctuation: true
+        }),
+        natural.compare("IT.", "it")
+    );
+    assert_eq!(None, natural.compare("its", "it"));
+    assert_eq!(
+        natural.compare("it?", "it"),
+        natural.compare("it", "it?"),
+        "both sides are normalised the same way"
+    );
+    assert_eq!(None, Normalization::strict().compare("What", "what"));
+    assert_eq!(None, Normalization::strict().compare("it?", "it"));
+}
+
+#[test]
+fn test_punctuation_only_words_are_kept() {
+    assert_eq!("!!!", Normalization::strip_trailing_punctuation("!!!"));
+    assert_eq!("[lac]", Normalization::strip_trailing_punctuation("[lac]."));
+    assert_eq!("bye", Normalization::strip_trailing_punctuation("bye..."));
+}
+
+#[test]
+fn test_filler_words() {
+    let natural = Normalization::default();
+
+    assert!(natural.is_filler("please"));
+    assert!(natural.is_filler("Please,"));
+    assert!(natural.is_filler("THE"));
+    assert!(!natural.is_filler("time"));
+    assert!(!Normalization::without_fillers().is_filler("please"));
+    assert!(!Normalization::strict().is_filler("the"));
+
+    assert!(natural.allows(Leniency::default(), &[String::from("the")]));
+    assert!(!Normalization::without_fillers().allows(Leniency::default(), &[String::from("the")]));
+    assert!(!Normalization::strict().allows(
+        Leniency {
+            folded_case: true,
+            stripped_punctuation: false
+        },
+        &[]
+    ));
+}
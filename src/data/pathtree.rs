@@ -1,15 +1,93 @@
-use crate::util::treepath::TreePath;
-use std::{collections::HashMap, fmt::Debug};
+use crate::data::normalization::{Leniency, Normalization};
+use crate::data::synonyms::Synonyms;
+use crate::util::treepath::{PathToken, TreePath, TreePathErr};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    fmt,
+    fmt::Debug,
+};
 
-use serde::{Deserialize, Serialize};
+use serde::{ser::SerializeStruct, Deserialize, Serialize};
 
+#[derive(Debug, PartialEq)]
 pub enum PathTreeOk {
+    SetOk,
+    ShortcutSetOk { shortcut: String },
     DropOk,
 }
 
+#[derive(Debug, PartialEq, Clone)]
 pub enum PathTreeErr {
-    DropNodeDoesNotExist,
-    DropNodeIsNull,
+    EmptyPath,
+    NodeDoesNotExist {
+        path: String,
+    },
+    NodeIsNull {
+        path: String,
+    },
+    BrokenHierarchy {
+        path: String,
+    },
+    NoMatch {
+        path: String,
+    },
+    ShortcutTaken {
+        shortcut: String,
+    },
+    AmbiguousMatch {
+        path: String,
+        candidates: Vec<String>,
+    },
+    InvalidPath(TreePathErr),
+    InputTooLong {
+        length: usize,
+        limit: usize,
+    },
+}
+
+impl fmt::Display for PathTreeErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathTreeErr::EmptyPath => write!(f, "path to a node cannot be empty"),
+            PathTreeErr::NodeDoesNotExist { path } => write!(f, "node [{}] does not exist", path),
+            PathTreeErr::NodeIsNull { path } => write!(
+                f,
+                "node [{}] is a null node, null nodes can't be explicitly deleted",
+                path
+            ),
+            PathTreeErr::BrokenHierarchy { path } => write!(
+                f,
+                "the hierarchy of node [{}] is broken, the saved data is most likely corrupted",
+                path
+            ),
+            PathTreeErr::NoMatch { path } => write!(f, "[{}] doesn't match any path", path),
+            PathTreeErr::AmbiguousMatch { path, candidates } => write!(
+                f,
+                "[{}] is ambiguous, it matches all of: [{}]",
+                path,
+                candidates.join("], [")
+            ),
+            PathTreeErr::ShortcutTaken { shortcut } => {
+                write!(f, "shortcut [{}] is already taken", shortcut)
+            }
+            PathTreeErr::InvalidPath(the_err) => write!(f, "invalid path: {}", the_err),
+            PathTreeErr::InputTooLong { length, limit } => write!(
+                f,
+                "the input has {} words, but a command can't have more than {}",
+                length, limit
+            ),
+        }
+    }
+}
+
+impl From<TreePathErr> for PathTreeErr {
+    fn from(the_err: TreePathErr) -> Self {
+        match the_err {
+            TreePathErr::EmptyPath => PathTreeErr::EmptyPath,
+            other => PathTreeErr::InvalidPath(other),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
@@ -18,14 +96,120 @@ pub struct Node<T> {
     pub value: Option<T>,
 }
 
-enum DropType {
-    RemoveNode(String),
-    ActiveToNull(String),
+type TokenId = usize;
+
+const MAX_SKIPPED_FILLERS: usize = 16;
+const MAX_INPUT_TOKENS: usize = 256;
+
+// Tokens are never freed, even after every path using them is dropped. The
+// interner only grows with the number of distinct words ever added to the tree,
+// which stays small for the command and alias trees it serves.
+#[derive(Debug, Default)]
+struct TokenInterner {
+    ids: HashMap<String, TokenId>,
+    tokens: Vec<String>,
+    keys: HashMap<String, Vec<TokenId>>,
+}
+
+impl TokenInterner {
+    fn intern(&mut self, token: &str) -> TokenId {
+        if let Some(id) = self.ids.get(token) {
+            return *id;
+        }
+
+        let id = self.tokens.len();
+        self.tokens.push(token.to_owned());
+        self.ids.insert(token.to_owned(), id);
+        self.keys
+            .entry(Normalization::match_key(token))
+            .or_default()
+            .push(id);
+        id
+    }
+
+    fn get(&self, token: &str) -> Option<TokenId> {
+        self.ids.get(token).copied()
+    }
+
+    fn with_key(&self, word: &str) -> &[TokenId] {
+        self.keys
+            .get(&Normalization::match_key(word))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    fn resolve(&self, id: TokenId) -> &str {
+        self.tokens[id].as_str()
+    }
+}
+
+#[derive(Debug)]
+struct TrieNode<T> {
+    node: Node<T>,
+    children: HashMap<TokenId, TrieNode<T>>,
+}
+
+impl<T> TrieNode<T> {
+    fn new(value: Option<T>) -> Self {
+        Self {
+            node: Node {
+                share_count: 0,
+                value,
+            },
+            children: HashMap::new(),
+        }
+    }
+
+    fn subtree_len(&self) -> usize {
+        1 + self
+            .children
+            .values()
+            .map(|child| child.subtree_len())
+            .sum::<usize>()
+    }
+
+    fn forget_words(&self, id: TokenId, words: &mut HashMap<TokenId, usize>) {
+        if let Some(uses) = words.get_mut(&id) {
+            *uses -= 1;
+            if *uses == 0 {
+                words.remove(&id);
+            }
+        }
+        for (child_id, child) in &self.children {
+            child.forget_words(*child_id, words);
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
+struct FlatPathTree<T> {
+    tree: HashMap<String, Node<T>>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug, Deserialize)]
+#[serde(
+    try_from = "FlatPathTree<T>",
+    bound(deserialize = "T: Deserialize<'de> + Clone + Debug")
+)]
 pub struct PathTree<T> {
-    pub tree: HashMap<String, Node<T>>,
+    root: TrieNode<T>,
+    tokens: TokenInterner,
+    len: usize,
+    words: HashMap<TokenId, usize>,
+    default_normalization: Normalization,
+    normalizations: HashMap<String, Normalization>,
+    lenient_normalization: Normalization,
+    synonyms: Synonyms,
+}
+
+impl<T> Default for PathTree<T>
+where
+    T: Clone + Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<T> PathTree<T>
@@ -34,263 +218,659 @@ where
 {
     pub fn new() -> Self {
         Self {
-            tree: HashMap::new(),
+            root: TrieNode::new(None),
+            tokens: TokenInterner::default(),
+            len: 0,
+            words: HashMap::new(),
+            default_normalization: Normalization::strict(),
+            normalizations: HashMap::new(),
+            lenient_normalization: Normalization::strict(),
+            synonyms: Synonyms::new(),
         }
     }
 
-    pub fn set_by_path(&mut self, value: T, path: &str) {
-        if path.is_empty() {
-            panic!("ERROR: path to a node cannot be empty!");
+    pub fn set_synonyms(&mut self, synonyms: Synonyms) {
+        self.synonyms = synonyms;
+    }
+
+    pub fn synonyms(&self) -> &Synonyms {
+        &self.synonyms
+    }
+
+    pub fn synonyms_mut(&mut self) -> &mut Synonyms {
+        &mut self.synonyms
+    }
+
+    pub fn uses_word(&self, word: &str) -> bool {
+        matches!(self.tokens.get(word), Some(id) if self.words.contains_key(&id))
+    }
+
+    fn synonym_for(&self, word: &str) -> Option<(&str, Leniency)> {
+        let lenient = &self.lenient_normalization;
+        let mut leniency = Leniency::default();
+        let mut candidate = word;
+        if lenient.strip_punctuation {
+            candidate = Normalization::strip_trailing_punctuation(word);
+            leniency.stripped_punctuation = candidate != word;
+        }
+        leniency.folded_case = candidate.to_lowercase() != candidate;
+        if leniency.folded_case && !lenient.fold_case {
+            return None;
         }
 
-        let the_hierarchy = TreePath::get_path_hierarchy(&path);
-        let path = TreePath::create_path(path);
-        the_hierarchy
-            .into_iter()
-            .enumerate()
-            .for_each(|(index, one_path)| {
-                if index == path.len() - 1 {
-                    let inserted_node = self.tree.entry(one_path).or_insert(Node {
-                        share_count: 0,
-                        value: Some(value.to_owned()),
-                    });
+        self.synonyms
+            .get(candidate)
+            .map(|for_word| (for_word, leniency))
+    }
 
-                    inserted_node.share_count += 1;
+    pub fn set_default_normalization(&mut self, normalization: Normalization) {
+        self.default_normalization = normalization;
+        self.update_lenient_normalization();
+    }
 
-                    if inserted_node.value.is_none() {
-                        inserted_node.value = Some(value.to_owned());
-                    }
-                } else {
-                    let null_node = self.tree.entry(one_path).or_insert(Node {
-                        share_count: 0,
-                        value: None,
-                    });
+    pub fn set_normalization(
+        &mut self,
+        path: &str,
+        normalization: Normalization,
+    ) -> Result<PathTreeOk, PathTreeErr> {
+        let path = TreePath::prettify(path);
+        if !self.is_node_active(&path) {
+            return Err(PathTreeErr::NodeDoesNotExist { path });
+        }
 
-                    null_node.share_count += 1;
-                }
-            })
+        self.normalizations.insert(path, normalization);
+        self.update_lenient_normalization();
+        Ok(PathTreeOk::SetOk)
     }
 
-    pub fn set_by_path_with_shortcut(&mut self, value: T, path: &str) {
-        self.set_by_path(value.clone(), path);
+    pub fn normalization_for(&self, path: &str) -> &Normalization {
+        self.normalizations
+            .get(&TreePath::prettify(path))
+            .unwrap_or(&self.default_normalization)
+    }
+
+    fn update_lenient_normalization(&mut self) {
+        self.lenient_normalization = self.normalizations.values().fold(
+            self.default_normalization.clone(),
+            |lenient, normalization| lenient.union(normalization),
+        );
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn paths(&self) -> Vec<String> {
+        self.iter().map(|(path, _)| path).collect()
+    }
+
+    pub fn iter(&self) -> SubtreeIter<'_, T> {
+        SubtreeIter::new(&self.tokens, String::new(), &self.root)
+    }
+
+    pub fn active_entries(&self) -> impl Iterator<Item = (String, &T)> {
+        self.iter()
+            .filter_map(|(path, node)| node.value.as_ref().map(|value| (path, value)))
+    }
+
+    pub fn walk(&self, prefix: &str) -> Result<SubtreeIter<'_, T>, PathTreeErr> {
+        let prefix = TreePath::prettify(prefix);
+        if prefix.is_empty() {
+            return Ok(self.iter());
+        }
+
+        match self.find(&prefix) {
+            Some(trie_node) => Ok(SubtreeIter {
+                tokens: &self.tokens,
+                stack: vec![(prefix, trie_node)],
+            }),
+            None => Err(PathTreeErr::NodeDoesNotExist { path: prefix }),
+        }
+    }
 
-        let mut shortcut_name = TreePath::create_shortcut(path, 1);
-        if !self.is_node_active(&shortcut_name) {
-            self.set_by_path(value.clone(), &shortcut_name);
+    pub fn children(&self, prefix: &str) -> Result<Vec<String>, PathTreeErr> {
+        let prefix = TreePath::prettify(prefix);
+        let trie_node = if prefix.is_empty() {
+            &self.root
         } else {
-            let mut alias_serial_number: usize = 2;
-            shortcut_name = loop {
-                let current_alias = TreePath::create_shortcut(path, alias_serial_number);
-                if !self.is_node_active(&current_alias) {
-                    break current_alias;
-                } else {
+            self.find(&prefix)
+                .ok_or_else(|| PathTreeErr::NodeDoesNotExist {
+                    path: prefix.clone(),
+                })?
+        };
+
+        Ok(
+            SubtreeIter::sorted_children(&self.tokens, &prefix, trie_node)
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect(),
+        )
+    }
+
+    pub fn count_active_descendants(&self, prefix: &str) -> Result<usize, PathTreeErr> {
+        let prefix = TreePath::prettify(prefix);
+        Ok(self
+            .walk(&prefix)?
+            .filter(|(path, node)| node.value.is_some() && *path != prefix)
+            .count())
+    }
+
+    fn find(&self, path: &str) -> Option<&TrieNode<T>> {
+        let mut current = &self.root;
+        let mut is_empty = true;
+
+        for token in path.split_whitespace() {
+            current = current.children.get(&self.tokens.get(token)?)?;
+            is_empty = false;
+        }
+
+        if is_empty {
+            None
+        } else {
+            Some(current)
+        }
+    }
+
+    fn token_ids(&self, path: &str) -> Option<Vec<TokenId>> {
+        path.split_whitespace()
+            .map(|token| self.tokens.get(token))
+            .collect()
+    }
+
+    pub fn set_by_path(&mut self, value: T, path: &str) -> Result<PathTreeOk, PathTreeErr> {
+        let path: Vec<TokenId> = path
+            .split_whitespace()
+            .map(|token| self.tokens.intern(token))
+            .collect();
+        if path.is_empty() {
+            return Err(PathTreeErr::EmptyPath);
+        }
+
+        let mut current = &mut self.root;
+        for id in path {
+            let (len, words) = (&mut self.len, &mut self.words);
+            current = current.children.entry(id).or_insert_with(|| {
+                *len += 1;
+                *words.entry(id).or_insert(0) += 1;
+                TrieNode::new(None)
+            });
+            current.node.share_count += 1;
+        }
+
+        if current.node.value.is_none() {
+            current.node.value = Some(value);
+        }
+
+        Ok(PathTreeOk::SetOk)
+    }
+
+    pub fn set_by_path_with_shortcut(
+        &mut self,
+        value: T,
+        path: &str,
+        preferred_shortcut: Option<&str>,
+    ) -> Result<PathTreeOk, PathTreeErr> {
+        let shortcut_name = match preferred_shortcut {
+            Some(shortcut) => {
+                let shortcut_path = TreePath::create_shortcut_path(shortcut, path)?;
+                if self.is_node_active(&shortcut_path) {
+                    return Err(PathTreeErr::ShortcutTaken {
+                        shortcut: shortcut_path,
+                    });
+                }
+                shortcut_path
+            }
+            None => {
+                let mut alias_serial_number: usize = 1;
+                loop {
+                    let current_alias = TreePath::create_shortcut(path, alias_serial_number)?;
+                    if !self.is_node_active(&current_alias) {
+                        break current_alias;
+                    }
                     alias_serial_number += 1;
                 }
             }
-        }
-        self.set_by_path(value, &shortcut_name);
+        };
+
+        self.set_by_path(value.clone(), path)?;
+        self.set_by_path(value, &shortcut_name)?;
+        Ok(PathTreeOk::ShortcutSetOk {
+            shortcut: shortcut_name,
+        })
     }
 
     pub fn get_by_path(&self, path: &str) -> Option<&Node<T>> {
-        let path = TreePath::create_path(&path);
-
-        self.tree.get(&path.join(" "))
+        self.find(path).map(|trie_node| &trie_node.node)
     }
 
-    fn flag_hierarchy_for_dropping(&mut self, path: &str) -> Vec<DropType> {
-        let hierarchy = TreePath::get_path_hierarchy(path);
-        let mut drops: Vec<DropType> = vec![];
+    pub fn get_value_by_path(&self, path: &str) -> Option<&T> {
+        self.get_by_path(path).and_then(|node| node.value.as_ref())
+    }
 
-        for (path_index, single_path) in hierarchy.iter().enumerate() {
-            match self.tree.get_mut(single_path) {
-                None => unreachable!("!!! PathTree::flag_hierarchy_for_dropping(): node does not exist !!! Most likely the aliases JSON file is corrupted."),
-                Some(node) => {
-                    node.share_count -= 1;
-                    if node.share_count == 0 {
-                        drops.push(DropType::RemoveNode(single_path.to_owned()));
-                    } else if node.value.is_some() && path_index == hierarchy.len() - 1 {
-                        drops.push(DropType::ActiveToNull(single_path.to_owned()));
-                    }
-                },
+    fn is_hierarchy_intact(&self, path: &[TokenId]) -> bool {
+        let mut current = &self.root;
+        for id in path {
+            match current.children.get(id) {
+                Some(child) if child.node.share_count > 0 => current = child,
+                _ => return false,
             }
         }
 
-        drops
+        true
     }
 
-    fn drop_hierarchy(&mut self, path: &str) {
-        let drops = self.flag_hierarchy_for_dropping(path);
+    fn drop_hierarchy(
+        children: &mut HashMap<TokenId, TrieNode<T>>,
+        path: &[TokenId],
+        len: &mut usize,
+        words: &mut HashMap<TokenId, usize>,
+    ) {
+        let (id, rest) = match path.split_first() {
+            Some(split) => split,
+            None => return,
+        };
 
-        for drop in drops {
-            match drop {
-                DropType::RemoveNode(path) => {
-                    self.tree.remove(&path).expect("ERROR: drop_hierarchy(): remove node failed, something is wrong with PathTree::flag_hierarchy() logic.");
-                }
-                DropType::ActiveToNull(path) => {
-                    let node = self.tree.get_mut(&path).expect("ERROR: drop_hierarchy(): setting active node to null failed, something is wrong with PathTree::flag_hierarchy() logic.");
-                    if node.value.is_none() {
-                        panic!("ERROR: PathTree::drop_hierarchy(): tried to set an active node to null, but it's already a null node.");
-                    }
-                    node.value = None;
+        let remove_child = match children.get_mut(id) {
+            None => return,
+            Some(child) => {
+                child.node.share_count -= 1;
+                if rest.is_empty() && child.node.share_count != 0 {
+                    child.node.value = None;
+                } else {
+                    PathTree::drop_hierarchy(&mut child.children, rest, len, words);
                 }
+
+                child.node.share_count == 0
+            }
+        };
+
+        if remove_child {
+            if let Some(removed) = children.remove(id) {
+                *len -= removed.subtree_len();
+                removed.forget_words(*id, words);
             }
         }
     }
 
     pub fn drop_by_path(&mut self, path: &str) -> Result<PathTreeOk, PathTreeErr> {
+        if TreePath::create_path(path).is_empty() {
+            return Err(PathTreeErr::EmptyPath);
+        }
+
         match self.get_by_path(path) {
-            None => Err(PathTreeErr::DropNodeDoesNotExist),
-            Some(node) => {
-                if node.value.is_some() {
-                    self.drop_hierarchy(path);
-                    Ok(PathTreeOk::DropOk)
-                } else {
-                    Err(PathTreeErr::DropNodeIsNull)
+            None => Err(PathTreeErr::NodeDoesNotExist {
+                path: TreePath::prettify(path),
+            }),
+            Some(node) if node.value.is_none() => Err(PathTreeErr::NodeIsNull {
+                path: TreePath::prettify(path),
+            }),
+            Some(_) => {
+                let ids = self.token_ids(path).unwrap_or_default();
+                if !self.is_hierarchy_intact(&ids) {
+                    return Err(PathTreeErr::BrokenHierarchy {
+                        path: TreePath::prettify(path),
+                    });
                 }
+
+                PathTree::drop_hierarchy(
+                    &mut self.root.children,
+                    &ids,
+                    &mut self.len,
+                    &mut self.words,
+                );
+                if !self.is_node_active(path) {
+                    self.normalizations.remove(&TreePath::prettify(path));
+                    self.update_lenient_normalization();
+                }
+                Ok(PathTreeOk::DropOk)
             }
         }
     }
 
     pub fn does_node_exist(&self, path: &str) -> bool {
-        self.tree.contains_key(&TreePath::prettify(path))
+        self.find(path).is_some()
     }
 
     pub fn is_node_null(&self, path: &str) -> bool {
-        self.does_node_exist(path) && self.tree.get(path).unwrap().value.is_none()
+        matches!(self.get_by_path(path), Some(node) if node.value.is_none())
     }
 
     pub fn is_node_active(&self, path: &str) -> bool {
-        self.does_node_exist(path) && !self.is_node_null(path)
+        matches!(self.get_by_path(path), Some(node) if node.value.is_some())
     }
 
-    fn attempt_multiword_parsing(&self, path: &str) -> Option<(String, Vec<String>)> {
-        let nodes = TreePath::create_path(path);
-        let mut slice_indices: (Vec<u32>, Vec<u32>) = (vec![], vec![]);
-        let mut args: Vec<String> = vec![];
-        let mut resulting_pathvec: Vec<String> = vec![];
+    fn collect_matches<'a>(
+        &'a self,
+        trie_node: &'a TrieNode<T>,
+        mut tokens: &[PathToken],
+        state: &mut MatchState<'a>,
+        matches: &mut Vec<PathMatch>,
+    ) {
+        let lenient = &self.lenient_normalization;
+        let mut filler_ids: Vec<TokenId> = lenient
+            .fillers
+            .iter()
+            .flat_map(|filler| self.tokens.with_key(filler).iter().copied())
+            .collect();
+        filler_ids.sort_unstable();
+        filler_ids.dedup();
+        let fillers_before = state.skipped_fillers.len();
+
+        loop {
+            let can_skip_filler = state.skipped_fillers.len() < MAX_SKIPPED_FILLERS;
+            for id in filler_ids.iter().filter(|_| can_skip_filler) {
+                let node = self.tokens.resolve(*id);
+                match trie_node.children.get(id) {
+                    Some(child) if lenient.is_filler(node) => {
+                        state.path.push(node);
+                        state.skipped_fillers.push(node.to_owned());
+                        self.collect_matches(child, tokens, state, matches);
+                        state.skipped_fillers.pop();
+                        state.path.pop();
+                    }
+                    _ => (),
+                }
+            }
+
+            let (token, rest) = match tokens.split_first() {
+                Some(split) => split,
+                None => {
+                    let path = state.path.join(" ");
+                    if trie_node.node.value.is_some()
+                        && self
+                            .normalization_for(&path)
+                            .allows(state.leniency, &state.skipped_fillers)
+                    {
+                        matches.push(PathMatch {
+                            path,
+                            args: state.args.clone(),
+                            specificity: state.specificity,
+                        });
+                    }
+                    break;
+                }
+            };
+            let is_word = !token.quoted && token.text != "<ARG>";
 
-        let mut loop_validation = true;
-        nodes.iter().enumerate().for_each(|(index, node)| {
-            if node.starts_with('\"') && node.len() == 1 {
-                loop_validation = false;
-                return;
+            let literal = match self.tokens.get(&token.text) {
+                Some(id) if is_word => trie_node
+                    .children
+                    .get(&id)
+                    .map(|child| (self.tokens.resolve(id), child)),
+                _ => None,
+            };
+            if let Some((node, child)) = literal {
+                state.path.push(node);
+                state.specificity += 1;
+                self.collect_matches(child, rest, state, matches);
+                state.specificity -= 1;
+                state.path.pop();
             }
 
-            if node.starts_with('\"') {
-                slice_indices.0.push(index as u32);
+            if is_word && (lenient.fold_case || lenient.strip_punctuation) {
+                for id in self.tokens.with_key(&token.text) {
+                    let child = match trie_node.children.get(id) {
+                        Some(child) => child,
+                        None => continue,
+                    };
+                    let node = self.tokens.resolve(*id);
+                    let leniency = match lenient.compare(&token.text, node) {
+                        Some(leniency) if node != "<ARG>" && leniency != Leniency::default() => {
+                            leniency
+                        }
+                        _ => continue,
+                    };
+
+                    let previous_leniency = state.leniency;
+                    state.leniency = previous_leniency.combine(leniency);
+                    state.path.push(node);
+                    state.specificity += 1;
+                    self.collect_matches(child, rest, state, matches);
+                    state.specificity -= 1;
+                    state.path.pop();
+                    state.leniency = previous_leniency;
+                }
             }
-            if node.ends_with('\"') {
-                slice_indices.1.push(index as u32);
+
+            let synonym = self.synonym_for(&token.text).filter(|_| is_word).and_then(
+                |(for_word, leniency)| {
+                    let id = self.tokens.get(for_word)?;
+                    trie_node
+                        .children
+                        .get(&id)
+                        .map(|child| (self.tokens.resolve(id), child, leniency))
+                },
+            );
+            if let Some((node, child, leniency)) = synonym {
+                let previous_leniency = state.leniency;
+                state.leniency = previous_leniency.combine(leniency);
+                state.path.push(node);
+                state.specificity += 1;
+                self.collect_matches(child, rest, state, matches);
+                state.specificity -= 1;
+                state.path.pop();
+                state.leniency = previous_leniency;
             }
-        });
-        if !loop_validation {
-            return None;
+
+            let argument = self
+                .tokens
+                .get("<ARG>")
+                .and_then(|id| trie_node.children.get(&id));
+            if let Some(child) = argument {
+                state.path.push("<ARG>");
+                state.args.push(token.text.clone());
+                self.collect_matches(child, rest, state, matches);
+                state.args.pop();
+                state.path.pop();
+            }
+
+            if !(can_skip_filler && is_word && lenient.is_filler(&token.text)) {
+                break;
+            }
+            state.skipped_fillers.push(token.text.clone());
+            tokens = rest;
         }
 
-        if slice_indices.0.is_empty() || slice_indices.1.is_empty() {
-            return None;
+        state.skipped_fillers.truncate(fillers_before);
+    }
+
+    pub fn match_all(&self, input: &str) -> Result<Vec<PathMatch>, PathTreeErr> {
+        let tokens = TreePath::tokenize(input)?;
+        if tokens.is_empty() {
+            return Err(PathTreeErr::EmptyPath);
         }
-        if slice_indices.0.len() != slice_indices.1.len() {
-            return None;
+        if tokens.len() > MAX_INPUT_TOKENS {
+            return Err(PathTreeErr::InputTooLong {
+                length: tokens.len(),
+                limit: MAX_INPUT_TOKENS,
+            });
         }
 
-        let mut previous_end_index: u32 = 0;
-        let mut loop_validation = true;
-        slice_indices
-            .0
-            .iter()
-            .enumerate()
-            .for_each(|(index, start)| {
-                let end = slice_indices.1.get(index).unwrap();
-                if *start > *end || *start < previous_end_index {
-                    loop_validation = false;
-                }
+        let mut matches: Vec<PathMatch> = vec![];
+        self.collect_matches(
+            &self.root,
+            &tokens,
+            &mut MatchState::default(),
+            &mut matches,
+        );
+        matches.sort_by(|left, right| {
+            right
+                .specificity
+                .cmp(&left.specificity)
+                .then_with(|| left.path.cmp(&right.path))
+        });
+
+        let mut unique_matches: Vec<PathMatch> = vec![];
+        for candidate in matches {
+            if !unique_matches
+                .iter()
+                .any(|seen| seen.path == candidate.path && seen.args == candidate.args)
+            {
+                unique_matches.push(candidate);
+            }
+        }
 
-                previous_end_index = *end;
+        Ok(unique_matches)
+    }
+
+    pub fn resolve(&self, input: &str) -> Result<PathMatch, PathTreeErr> {
+        let mut matches = self.match_all(input)?;
+        if matches.is_empty() {
+            return Err(PathTreeErr::NoMatch {
+                path: TreePath::prettify(input),
             });
-        if !loop_validation {
-            return None;
         }
 
-        for arg_number in 0..slice_indices.0.len() {
-            let lower_index = slice_indices.0[arg_number] as usize;
-            let upper_index = slice_indices.1[arg_number] as usize;
+        let best_specificity = matches[0].specificity;
+        matches.retain(|candidate| candidate.specificity == best_specificity);
+        if matches.len() > 1 {
+            return Err(PathTreeErr::AmbiguousMatch {
+                path: TreePath::prettify(input),
+                candidates: matches
+                    .into_iter()
+                    .map(|candidate| candidate.path)
+                    .collect(),
+            });
+        }
 
-            let mut new_arg: Vec<String> = vec![];
-            new_arg.extend_from_slice(&nodes[lower_index..=upper_index]);
-            let mut new_arg = new_arg.join(" ");
+        Ok(matches.remove(0))
+    }
 
-            new_arg.remove(0);
-            new_arg.remove(new_arg.len() - 1);
+    pub fn get_command_and_args_from_path(&self, path: &str) -> Option<(String, Vec<String>)> {
+        self.resolve(path)
+            .ok()
+            .map(|best_match| (best_match.path, best_match.args))
+    }
+}
 
-            args.push(new_arg);
-        }
+#[derive(Default)]
+struct MatchState<'a> {
+    path: Vec<&'a str>,
+    args: Vec<String>,
+    specificity: usize,
+    leniency: Leniency,
+    skipped_fillers: Vec<String>,
+}
 
-        let mut started: bool = false;
-        for node in &nodes {
-            if node.starts_with('\"') {
-                started = true;
-                resulting_pathvec.push("<ARG>".to_owned());
-            }
+#[derive(Debug, PartialEq, Clone)]
+pub struct PathMatch {
+    pub path: String,
+    pub args: Vec<String>,
+    pub specificity: usize,
+}
 
-            if !node.starts_with('\"') && !node.ends_with('\"') && !started {
-                resulting_pathvec.push(node.clone());
-            }
+pub struct SubtreeIter<'a, T> {
+    tokens: &'a TokenInterner,
+    stack: Vec<(String, &'a TrieNode<T>)>,
+}
 
-            if node.ends_with('\"') {
-                started = false;
-            }
-        }
+impl<'a, T> SubtreeIter<'a, T> {
+    fn new(tokens: &'a TokenInterner, prefix: String, trie_node: &'a TrieNode<T>) -> Self {
+        let mut stack = SubtreeIter::sorted_children(tokens, &prefix, trie_node);
+        stack.reverse();
 
-        let resulting_path = resulting_pathvec.join(" ");
-        if self.is_node_active(resulting_path.as_str()) {
-            Some((resulting_path, args))
-        } else {
-            None
-        }
+        Self { tokens, stack }
     }
 
-    fn attempt_single_word_parsing(&self, path: &str) -> Option<(String, Vec<String>)> {
-        let pathvec = TreePath::create_path(path);
-        let mut args: Vec<String> = vec![];
-        let mut argumented: Vec<String> = vec![];
+    fn sorted_children(
+        tokens: &TokenInterner,
+        prefix: &str,
+        trie_node: &'a TrieNode<T>,
+    ) -> Vec<(String, &'a TrieNode<T>)> {
+        let mut children: Vec<(&str, &TrieNode<T>)> = trie_node
+            .children
+            .iter()
+            .map(|(id, child)| (tokens.resolve(*id), child))
+            .collect();
+        children.sort_by_key(|(token, _)| *token);
 
-        for path in TreePath::get_path_hierarchy(&pathvec.join(" ")) {
-            let previous_state = argumented.clone();
-            argumented = TreePath::create_path(&TreePath::append_path_node(
-                &argumented,
-                TreePath::get_last_node(&path).unwrap().as_str(),
-            ));
-            if self.does_node_exist(&argumented.join(" ")) && argumented.last().unwrap() == "<ARG>"
-            {
-                args.push("<ARG>".to_owned());
-                continue;
-            } else if self.does_node_exist(&argumented.join(" ")) {
-                continue;
-            } else {
-                let argified_from_previous = TreePath::append_path_node(&previous_state, "<ARG>");
-                if self.does_node_exist(&argified_from_previous) {
-                    argumented = TreePath::create_path(&argified_from_previous);
-                    args.push(TreePath::get_last_node(&path).unwrap());
-                } else {
-                    return None;
-                }
-            }
-        }
+        children
+            .into_iter()
+            .map(|(token, child)| (TreePath::extend_path(prefix, token), child))
+            .collect()
+    }
+}
 
-        if args.iter().any(|arg| arg == "\"") {
-            return None;
-        }
+impl<'a, T> Iterator for SubtreeIter<'a, T> {
+    type Item = (String, &'a Node<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, trie_node) = self.stack.pop()?;
 
-        Some((argumented.join(" "), args))
+        let mut children = SubtreeIter::sorted_children(self.tokens, &path, trie_node);
+        children.reverse();
+        self.stack.extend(children);
+
+        Some((path, &trie_node.node))
     }
+}
 
-    pub fn get_command_and_args_from_path(&self, path: &str) -> Option<(String, Vec<String>)> {
-        if let Some((mw_command, mw_args)) = self.attempt_multiword_parsing(path) {
-            Some((mw_command, mw_args))
-        } else {
-            self.attempt_single_word_parsing(path)
+impl<T> Serialize for PathTree<T>
+where
+    T: Clone + Debug + Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let tree: BTreeMap<String, &Node<T>> = self.iter().collect();
+
+        let mut state = serializer.serialize_struct("PathTree", 1)?;
+        state.serialize_field("tree", &tree)?;
+        state.end()
+    }
+}
+
+impl<T> TryFrom<FlatPathTree<T>> for PathTree<T>
+where
+    T: Clone + Debug,
+{
+    type Error = PathTreeErr;
+
+    fn try_from(flat: FlatPathTree<T>) -> Result<Self, Self::Error> {
+        let mut flat_nodes: Vec<(Vec<String>, Node<T>)> = flat
+            .tree
+            .into_iter()
+            .map(|(path, node)| (TreePath::create_path(&path), node))
+            .collect();
+        flat_nodes.sort_by_key(|(path, _)| path.len());
+
+        let mut path_tree = PathTree::new();
+        for (path, node) in flat_nodes {
+            let broken = || PathTreeErr::BrokenHierarchy {
+                path: path.join(" "),
+            };
+            let (last, parent_path) = path.split_last().ok_or(PathTreeErr::EmptyPath)?;
+            if node.share_count == 0 {
+                return Err(broken());
+            }
+
+            let id = path_tree.tokens.intern(last);
+            let mut parent = &mut path_tree.root;
+            for token in parent_path {
+                let parent_id = path_tree.tokens.get(token).ok_or_else(broken)?;
+                parent = parent.children.get_mut(&parent_id).ok_or_else(broken)?;
+            }
+            if !parent_path.is_empty() && parent.node.share_count < node.share_count {
+                return Err(broken());
+            }
+            if parent.children.contains_key(&id) {
+                return Err(broken());
+            }
+
+            parent.children.insert(
+                id,
+                TrieNode {
+                    node,
+                    children: HashMap::new(),
+                },
+            );
+            path_tree.len += 1;
+            *path_tree.words.entry(id).or_insert(0) += 1;
         }
+
+        Ok(path_tree)
     }
 }
 
@@ -321,13 +901,16 @@ fn test_path_hierarchy() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_tree_setters_and_getters() {
     let mut test_tree = PathTree::new();
 
-    test_tree.set_by_path(
-        "test garbage val".to_string(),
-        "そっか おふの $%?рашин /fourth .fifth \\sixth",
-    );
+    test_tree
+        .set_by_path(
+            "test garbage val".to_string(),
+            "そっか おふの $%?рашин /fourth .fifth \\sixth",
+        )
+        .unwrap();
     assert_eq!(false, test_tree.is_node_active("そっか"));
     assert_eq!(true, test_tree.does_node_exist("そっか"));
     assert_eq!(false, test_tree.is_node_active("そっか おふの"));
@@ -391,12 +974,18 @@ fn test_tree_setters_and_getters() {
 }
 
 #[test]
+#[allow(
+    clippy::bool_assert_comparison,
+    clippy::unnecessary_owned_empty_strings
+)]
 fn check_empty_path_creation() {
     let mut test_tree = PathTree::new();
-    test_tree.set_by_path(
-        "test garbage val".to_string(),
-        "そっか おふの $%?рашин /fourth .fifth \\sixth",
-    );
+    test_tree
+        .set_by_path(
+            "test garbage val".to_string(),
+            "そっか おふの $%?рашин /fourth .fifth \\sixth",
+        )
+        .unwrap();
 
     assert_eq!(
         Vec::<String>::new(),
@@ -409,18 +998,28 @@ fn check_empty_path_creation() {
 }
 
 #[test]
-#[should_panic(expected = "ERROR: path to a node cannot be empty!")]
-fn test_setting_node_with_empty_path_panics() {
+fn test_setting_node_with_empty_path_fails() {
     let mut test_tree = PathTree::new();
-    test_tree.set_by_path("value".to_string(), "");
+    assert_eq!(
+        Err(PathTreeErr::EmptyPath),
+        test_tree.set_by_path("value".to_string(), "")
+    );
+    assert_eq!(
+        Err(PathTreeErr::EmptyPath),
+        test_tree.set_by_path("value".to_string(), "   \n ")
+    );
+    assert!(test_tree.is_empty());
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_pathing_works_with_untrimmed_paths() {
     let mut test_tree = PathTree::new();
     let path = "        something           completely      bonkers       \n";
 
-    test_tree.set_by_path("test garbage val".to_string(), path);
+    test_tree
+        .set_by_path("test garbage val".to_string(), path)
+        .unwrap();
 
     assert_eq!(false, test_tree.is_node_active("something"));
     assert_eq!(true, test_tree.does_node_exist("something"));
@@ -448,24 +1047,32 @@ fn test_pathing_works_with_untrimmed_paths() {
         test_tree.get_by_path("something completely bonkers")
     );
 
-    assert_eq!(true, test_tree.tree.contains_key("something"));
-    assert_eq!(true, test_tree.tree.contains_key("something completely"));
+    assert_eq!(true, test_tree.does_node_exist("something"));
+    assert_eq!(true, test_tree.does_node_exist("something completely"));
     assert_eq!(
         true,
-        test_tree.tree.contains_key("something completely bonkers")
+        test_tree.does_node_exist("something completely bonkers")
     );
 }
 
 #[test]
 fn check_argumented_paths() {
     let mut test_tree = PathTree::new();
-    test_tree.set_by_path("garbage", "this is <ARG> another <ARG>");
-    test_tree.set_by_path("garbage", "oh my god its <ARG> working <ARG> !!!");
-    test_tree.set_by_path("garbage", "<ARG> I cant believe <ARG>");
-    test_tree.set_by_path(
-        "garbage",
-        "<ARG> and <ARG> and <ARG> and <ARG> and <ARG> and KEKW",
-    );
+    test_tree
+        .set_by_path("garbage", "this is <ARG> another <ARG>")
+        .unwrap();
+    test_tree
+        .set_by_path("garbage", "oh my god its <ARG> working <ARG> !!!")
+        .unwrap();
+    test_tree
+        .set_by_path("garbage", "<ARG> I cant believe <ARG>")
+        .unwrap();
+    test_tree
+        .set_by_path(
+            "garbage",
+            "<ARG> and <ARG> and <ARG> and <ARG> and <ARG> and KEKW",
+        )
+        .unwrap();
 
     assert_eq!(
         Some((
@@ -546,3 +1153,717 @@ fn check_argumented_paths() {
             .get_command_and_args_from_path("one and two and three and four and five and KEKW")
     );
 }
+
+#[test]
+fn test_shortcut_creation_with_empty_path_fails() {
+    assert_eq!(
+        Err(TreePathErr::EmptyPath),
+        TreePath::create_shortcut("", 1)
+    );
+    assert_eq!(
+        Err(TreePathErr::EmptyPath),
+        TreePath::create_shortcut("  \t ", 2)
+    );
+    assert_eq!(
+        Ok(String::from("[raa] <ARG>")),
+        TreePath::create_shortcut("remove alias <ARG>", 1)
+    );
+    assert_eq!(
+        Ok(String::from("[raa3] <ARG>")),
+        TreePath::create_shortcut("remove alias <ARG>", 3)
+    );
+
+    let mut test_tree: PathTree<String> = PathTree::new();
+    assert_eq!(
+        Err(PathTreeErr::EmptyPath),
+        test_tree.set_by_path_with_shortcut("value".to_string(), " ", None)
+    );
+    assert!(test_tree.is_empty());
+}
+
+#[test]
+fn test_preferred_shortcuts() {
+    let mut test_tree: PathTree<String> = PathTree::new();
+    assert_eq!(
+        Ok(PathTreeOk::ShortcutSetOk {
+            shortcut: String::from("[rsa] <ARG>")
+        }),
+        test_tree.set_by_path_with_shortcut(
+            "synonym".to_string(),
+            "remove synonym <ARG>",
+            Some("[rsa]")
+        )
+    );
+    assert_eq!(
+        Err(PathTreeErr::ShortcutTaken {
+            shortcut: String::from("[rsa] <ARG>")
+        }),
+        test_tree.set_by_path_with_shortcut(
+            "shortcut".to_string(),
+            "remove shortcut <ARG>",
+            Some("[rsa]")
+        )
+    );
+    assert!(!test_tree.is_node_active("remove shortcut <ARG>"));
+    assert_eq!(
+        Ok(PathTreeOk::ShortcutSetOk {
+            shortcut: String::from("[rsha] <ARG>")
+        }),
+        test_tree.set_by_path_with_shortcut(
+            "shortcut".to_string(),
+            "remove shortcut <ARG>",
+            Some("[rsha]")
+        )
+    );
+    assert_eq!(
+        Some(&String::from("shortcut")),
+        test_tree.get_value_by_path("[rsha] <ARG>")
+    );
+    assert_eq!(
+        Some(&String::from("synonym")),
+        test_tree.get_value_by_path("[rsa] <ARG>")
+    );
+}
+
+#[test]
+fn test_creating_shortcut_paths() {
+    assert_eq!(
+        Ok(String::from("[say] <ARG> <ARG>")),
+        TreePath::create_shortcut_path(" [say] ", "please say <ARG> and <ARG>")
+    );
+    assert_eq!(
+        Ok(String::from("[t]")),
+        TreePath::create_shortcut_path("[t]", "what time is it")
+    );
+    for invalid in ["t", "[]", "[t] [x]", "[t", "[[t]]", ""].iter() {
+        assert_eq!(
+            Err(TreePathErr::InvalidShortcut {
+                shortcut: TreePath::prettify(invalid)
+            }),
+            TreePath::create_shortcut_path(invalid, "what time is it")
+        );
+    }
+    assert_eq!(
+        Some(String::from("[say]")),
+        TreePath::get_shortcut_name("[say] <ARG> <ARG>")
+    );
+    assert_eq!(None, TreePath::get_shortcut_name("say <ARG>"));
+}
+
+#[test]
+fn test_shortcut_detection_with_empty_path() {
+    assert!(!TreePath::is_path_a_shortcut(""));
+    assert!(!TreePath::is_path_a_shortcut("   "));
+    assert!(!TreePath::is_path_a_shortcut("exit"));
+    assert!(TreePath::is_path_a_shortcut("[e]"));
+    assert!(TreePath::is_path_a_shortcut("  [psaaa] <ARG> <ARG>"));
+}
+
+#[test]
+fn test_reconstructing_path_with_missing_args_fails() {
+    assert_eq!(
+        Err(TreePathErr::NotEnoughArguments {
+            expected: 2,
+            provided: 1
+        }),
+        TreePath::reconstruct_argumented_path(
+            "please say <ARG> and <ARG>",
+            vec![String::from("one")]
+        )
+    );
+    assert_eq!(
+        Ok(String::from("please say \"one\" and \"two\"")),
+        TreePath::reconstruct_argumented_path(
+            "please say <ARG> and <ARG>",
+            vec![String::from("one"), String::from("two")]
+        )
+    );
+}
+
+#[test]
+fn test_node_state_checks_with_untrimmed_paths() {
+    let mut test_tree = PathTree::new();
+    test_tree
+        .set_by_path("value".to_string(), "one two")
+        .unwrap();
+
+    assert!(test_tree.is_node_null("  one "));
+    assert!(!test_tree.is_node_null(" one   two "));
+    assert!(test_tree.is_node_active(" one   two "));
+    assert!(!test_tree.is_node_null("three"));
+    assert!(!test_tree.is_node_active(""));
+}
+
+#[test]
+fn test_dropping_invalid_nodes_fails() {
+    let mut test_tree = PathTree::new();
+    test_tree
+        .set_by_path("value".to_string(), "one two")
+        .unwrap();
+
+    assert_eq!(Err(PathTreeErr::EmptyPath), test_tree.drop_by_path(" "));
+    assert_eq!(
+        Err(PathTreeErr::NodeDoesNotExist {
+            path: String::from("one three")
+        }),
+        test_tree.drop_by_path("one  three")
+    );
+    assert_eq!(
+        Err(PathTreeErr::NodeIsNull {
+            path: String::from("one")
+        }),
+        test_tree.drop_by_path("one")
+    );
+    assert_eq!(Ok(PathTreeOk::DropOk), test_tree.drop_by_path("one two"));
+    assert!(test_tree.is_empty());
+}
+
+#[test]
+fn test_loading_corrupted_hierarchy_fails() {
+    let corrupted = r#"{"tree": {"one two": {"share_count": 1, "value": "exit"}}}"#;
+    assert!(serde_json::from_str::<PathTree<String>>(corrupted).is_err());
+
+    let corrupted = r#"{"tree": {
+        "one": {"share_count": 0, "value": null},
+        "one two": {"share_count": 1, "value": "exit"}
+    }}"#;
+    assert!(serde_json::from_str::<PathTree<String>>(corrupted).is_err());
+
+    let corrupted = r#"{"tree": {
+        "one": {"share_count": 1, "value": null},
+        "one two": {"share_count": 2, "value": "exit"}
+    }}"#;
+    assert!(serde_json::from_str::<PathTree<String>>(corrupted).is_err());
+}
+
+#[test]
+fn test_serialization_roundtrip() {
+    let mut test_tree = PathTree::new();
+    test_tree
+        .set_by_path("exit".to_string(), "shut it down")
+        .unwrap();
+    test_tree
+        .set_by_path("remove alias <ARG>".to_string(), "shut <ARG>")
+        .unwrap();
+
+    let saved = serde_json::to_string(&test_tree).unwrap();
+    assert_eq!(
+        r#"{"tree":{"shut":{"share_count":2,"value":null},"shut <ARG>":{"share_count":1,"value":"remove alias <ARG>"},"shut it":{"share_count":1,"value":null},"shut it down":{"share_count":1,"value":"exit"}}}"#,
+        saved
+    );
+
+    let mut loaded: PathTree<String> = serde_json::from_str(&saved).unwrap();
+    assert_eq!(4, loaded.len());
+    assert_eq!(
+        Some(&String::from("exit")),
+        loaded.get_value_by_path("shut it down")
+    );
+    assert_eq!(Ok(PathTreeOk::DropOk), loaded.drop_by_path("shut it down"));
+    assert_eq!(2, loaded.len());
+    assert!(loaded.is_node_null("shut"));
+    assert!(loaded.is_node_active("shut <ARG>"));
+}
+
+#[cfg(test)]
+fn create_listing_tree() -> PathTree<&'static str> {
+    let mut test_tree = PathTree::new();
+    test_tree.set_by_path("time", "what time is it").unwrap();
+    test_tree.set_by_path("name", "what is your name").unwrap();
+    test_tree.set_by_path("is", "what is").unwrap();
+    test_tree.set_by_path("exit", "exit").unwrap();
+    test_tree
+        .set_by_path("remove", "remove alias <ARG>")
+        .unwrap();
+    test_tree
+}
+
+#[test]
+fn test_active_entries_are_sorted_depth_first() {
+    let test_tree = create_listing_tree();
+
+    assert_eq!(
+        vec![
+            (String::from("exit"), &"exit"),
+            (String::from("remove alias <ARG>"), &"remove"),
+            (String::from("what is"), &"is"),
+            (String::from("what is your name"), &"name"),
+            (String::from("what time is it"), &"time"),
+        ],
+        test_tree.active_entries().collect::<Vec<(String, &&str)>>()
+    );
+    assert_eq!(test_tree.len(), test_tree.paths().len());
+    assert_eq!(
+        vec![
+            "exit",
+            "remove",
+            "remove alias",
+            "remove alias <ARG>",
+            "what"
+        ],
+        test_tree.paths()[..5].to_vec()
+    );
+}
+
+#[test]
+fn test_children_of_prefix() {
+    let test_tree = create_listing_tree();
+
+    assert_eq!(
+        Ok(vec![
+            String::from("exit"),
+            String::from("remove"),
+            String::from("what")
+        ]),
+        test_tree.children("")
+    );
+    assert_eq!(
+        Ok(vec![String::from("what is"), String::from("what time")]),
+        test_tree.children("  what ")
+    );
+    assert_eq!(Ok(vec![]), test_tree.children("exit"));
+    assert_eq!(
+        Err(PathTreeErr::NodeDoesNotExist {
+            path: String::from("who")
+        }),
+        test_tree.children("who")
+    );
+}
+
+#[test]
+fn test_walking_a_subtree() {
+    let test_tree = create_listing_tree();
+
+    let walked: Vec<(String, Option<&str>)> = test_tree
+        .walk("what")
+        .unwrap()
+        .map(|(path, node)| (path, node.value))
+        .collect();
+    assert_eq!(
+        vec![
+            (String::from("what"), None),
+            (String::from("what is"), Some("is")),
+            (String::from("what is your"), None),
+            (String::from("what is your name"), Some("name")),
+            (String::from("what time"), None),
+            (String::from("what time is"), None),
+            (String::from("what time is it"), Some("time")),
+        ],
+        walked
+    );
+    assert_eq!(test_tree.len(), test_tree.walk("").unwrap().count());
+    assert!(test_tree.walk("what was").is_err());
+}
+
+#[test]
+fn test_counting_active_descendants() {
+    let test_tree = create_listing_tree();
+
+    assert_eq!(Ok(5), test_tree.count_active_descendants(""));
+    assert_eq!(Ok(3), test_tree.count_active_descendants("what"));
+    assert_eq!(Ok(1), test_tree.count_active_descendants("what is"));
+    assert_eq!(Ok(0), test_tree.count_active_descendants("exit"));
+    assert_eq!(
+        Err(PathTreeErr::NodeDoesNotExist {
+            path: String::from("nope")
+        }),
+        test_tree.count_active_descendants("nope")
+    );
+}
+
+#[test]
+fn test_tokenizing_quoted_arguments() {
+    let word = |text: &str| PathToken {
+        text: text.to_owned(),
+        quoted: false,
+    };
+    let quoted = |text: &str| PathToken {
+        text: text.to_owned(),
+        quoted: true,
+    };
+
+    assert_eq!(
+        Ok(vec![
+            word("please"),
+            word("say"),
+            quoted("one argument"),
+            word("and"),
+            quoted("two"),
+        ]),
+        TreePath::tokenize("please  say \"one   argument\" and \"two\"")
+    );
+    assert_eq!(
+        Ok(vec![word("say"), quoted(""), quoted("spaced out")]),
+        TreePath::tokenize("say \"\" \" spaced out \"")
+    );
+    assert_eq!(Ok(vec![word("it's")]), TreePath::tokenize("it's"));
+    assert_eq!(
+        Err(TreePathErr::UnclosedQuote),
+        TreePath::tokenize("please say \"one and two")
+    );
+}
+
+#[test]
+fn test_matcher_backtracks_over_literal_nodes() {
+    let mut test_tree = PathTree::new();
+    test_tree.set_by_path("twice", "say <ARG> twice").unwrap();
+    test_tree.set_by_path("world", "say hello world").unwrap();
+    test_tree
+        .set_by_path("please", "please say <ARG> and <ARG>")
+        .unwrap();
+
+    assert_eq!(
+        Some((String::from("say <ARG> twice"), vec![String::from("hello")])),
+        test_tree.get_command_and_args_from_path("say hello twice")
+    );
+    assert_eq!(
+        Some((String::from("say hello world"), vec![])),
+        test_tree.get_command_and_args_from_path("say hello world")
+    );
+    assert_eq!(
+        Some((
+            String::from("please say <ARG> and <ARG>"),
+            vec![String::from("and"), String::from("and")]
+        )),
+        test_tree.get_command_and_args_from_path("please say and and and")
+    );
+    assert_eq!(
+        Some((
+            String::from("please say <ARG> and <ARG>"),
+            vec![String::from("hello world"), String::from("<ARG>")]
+        )),
+        test_tree.get_command_and_args_from_path("please say \"hello world\" and <ARG>")
+    );
+}
+
+#[test]
+fn test_matcher_ranks_matches_by_specificity() {
+    let mut test_tree = PathTree::new();
+    test_tree
+        .set_by_path("literal", "explain command help")
+        .unwrap();
+    test_tree
+        .set_by_path("arg", "explain command <ARG>")
+        .unwrap();
+    test_tree
+        .set_by_path("two args", "explain <ARG> <ARG>")
+        .unwrap();
+
+    assert_eq!(
+        Ok(vec![
+            PathMatch {
+                path: String::from("explain command help"),
+                args: vec![],
+                specificity: 3
+            },
+            PathMatch {
+                path: String::from("explain command <ARG>"),
+                args: vec![String::from("help")],
+                specificity: 2
+            },
+            PathMatch {
+                path: String::from("explain <ARG> <ARG>"),
+                args: vec![String::from("command"), String::from("help")],
+                specificity: 1
+            },
+        ]),
+        test_tree.match_all("explain command help")
+    );
+    assert_eq!(
+        Ok(PathMatch {
+            path: String::from("explain command <ARG>"),
+            args: vec![String::from("help")],
+            specificity: 2
+        }),
+        test_tree.resolve("explain command \"help\"")
+    );
+}
+
+#[test]
+fn test_matcher_reports_ambiguity() {
+    let mut test_tree = PathTree::new();
+    test_tree
+        .set_by_path("alias", "remove alias <ARG>")
+        .unwrap();
+    test_tree.set_by_path("now", "remove <ARG> now").unwrap();
+
+    assert_eq!(
+        Err(PathTreeErr::AmbiguousMatch {
+            path: String::from("remove alias now"),
+            candidates: vec![
+                String::from("remove <ARG> now"),
+                String::from("remove alias <ARG>")
+            ]
+        }),
+        test_tree.resolve("remove alias now")
+    );
+    assert_eq!(
+        None,
+        test_tree.get_command_and_args_from_path("remove alias now")
+    );
+    assert_eq!(
+        Some((
+            String::from("remove alias <ARG>"),
+            vec![String::from("now")]
+        )),
+        test_tree.get_command_and_args_from_path("remove alias \"now\"")
+    );
+    assert_eq!(
+        Err(PathTreeErr::NoMatch {
+            path: String::from("remove alias")
+        }),
+        test_tree.resolve("remove   alias")
+    );
+    assert_eq!(
+        Err(PathTreeErr::InvalidPath(TreePathErr::UnclosedQuote)),
+        test_tree.resolve("remove alias \"now")
+    );
+    assert_eq!(Err(PathTreeErr::EmptyPath), test_tree.resolve("   "));
+}
+
+#[test]
+fn test_normalized_matching_leaves_arguments_untouched() {
+    let mut test_tree = PathTree::new();
+    test_tree.set_by_path("time", "what time is it").unwrap();
+    test_tree
+        .set_by_path("say", "please say <ARG> and <ARG>")
+        .unwrap();
+
+    assert_eq!(
+        None,
+        test_tree.get_command_and_args_from_path("What time is it?")
+    );
+
+    test_tree.set_default_normalization(Normalization::default());
+    assert_eq!(
+        Some((String::from("what time is it"), vec![])),
+        test_tree.get_command_and_args_from_path("What time is it?")
+    );
+    assert_eq!(
+        Some((String::from("what time is it"), vec![])),
+        test_tree.get_command_and_args_from_path("what time is it please")
+    );
+    assert_eq!(
+        Some((String::from("what time is it"), vec![])),
+        test_tree.get_command_and_args_from_path("WHAT the time is it.")
+    );
+    assert_eq!(
+        Some((
+            String::from("please say <ARG> and <ARG>"),
+            vec![String::from("Hi"), String::from("bye.")]
+        )),
+        test_tree.get_command_and_args_from_path("Please say Hi and bye.")
+    );
+    assert_eq!(
+        Some((
+            String::from("please say <ARG> and <ARG>"),
+            vec![String::from("the"), String::from("end")]
+        )),
+        test_tree.get_command_and_args_from_path("say the and end")
+    );
+}
+
+#[test]
+fn test_normalization_is_configurable_per_path() {
+    let mut test_tree = PathTree::new();
+    test_tree.set_by_path("time", "what time is it").unwrap();
+    test_tree
+        .set_by_path("remove", "remove alias <ARG>")
+        .unwrap();
+    test_tree.set_default_normalization(Normalization::default());
+
+    assert_eq!(
+        Err(PathTreeErr::NodeDoesNotExist {
+            path: String::from("remove alias")
+        }),
+        test_tree.set_normalization("remove alias", Normalization::strict())
+    );
+    assert_eq!(
+        Ok(PathTreeOk::SetOk),
+        test_tree.set_normalization("remove  alias <ARG>", Normalization::without_fillers())
+    );
+
+    assert_eq!(
+        Some((String::from("remove alias <ARG>"), vec![String::from("x")])),
+        test_tree.get_command_and_args_from_path("Remove alias x")
+    );
+    assert_eq!(
+        None,
+        test_tree.get_command_and_args_from_path("remove the alias x")
+    );
+    assert_eq!(
+        Some((String::from("what time is it"), vec![])),
+        test_tree.get_command_and_args_from_path("what the time is it")
+    );
+
+    test_tree.set_default_normalization(Normalization::strict());
+    test_tree
+        .set_normalization("what time is it", Normalization::default())
+        .unwrap();
+    assert_eq!(
+        Some((String::from("what time is it"), vec![])),
+        test_tree.get_command_and_args_from_path("What time is it?")
+    );
+    assert_eq!(
+        Some((String::from("remove alias <ARG>"), vec![String::from("x")])),
+        test_tree.get_command_and_args_from_path("REMOVE alias x")
+    );
+    assert_eq!(
+        None,
+        test_tree.get_command_and_args_from_path("remove alias x please")
+    );
+    assert_eq!(
+        &Normalization::without_fillers(),
+        test_tree.normalization_for("remove alias <ARG>")
+    );
+
+    test_tree.drop_by_path("what time is it").unwrap();
+    assert_eq!(
+        &Normalization::strict(),
+        test_tree.normalization_for("what time is it")
+    );
+}
+
+#[test]
+fn test_matching_with_synonyms() {
+    let mut test_tree = PathTree::new();
+    test_tree.set_by_path("time", "what time is it").unwrap();
+    test_tree
+        .set_by_path("remove", "remove alias <ARG>")
+        .unwrap();
+    test_tree.set_synonyms(Synonyms::with_defaults());
+
+    assert_eq!(
+        Some((String::from("what time is it"), vec![])),
+        test_tree.get_command_and_args_from_path("what clock is it")
+    );
+    assert_eq!(
+        Some((
+            String::from("remove alias <ARG>"),
+            vec![String::from("drop")]
+        )),
+        test_tree.get_command_and_args_from_path("drop alias drop")
+    );
+    assert_eq!(
+        None,
+        test_tree.get_command_and_args_from_path("what Clock is it")
+    );
+
+    test_tree.set_default_normalization(Normalization::default());
+    assert_eq!(
+        Some((String::from("what time is it"), vec![])),
+        test_tree.get_command_and_args_from_path("What Clock is it?")
+    );
+    assert_eq!(
+        Some((String::from("remove alias <ARG>"), vec![String::from("x")])),
+        test_tree.get_command_and_args_from_path("Delete, alias x")
+    );
+
+    test_tree.synonyms_mut().remove("clock");
+    assert_eq!(
+        None,
+        test_tree.get_command_and_args_from_path("what clock is it")
+    );
+    assert!(test_tree.uses_word("alias"));
+    assert!(!test_tree.uses_word("clock"));
+
+    test_tree.drop_by_path("remove alias <ARG>").unwrap();
+    assert!(!test_tree.uses_word("alias"));
+    assert!(test_tree.uses_word("time"));
+    let restored: PathTree<String> =
+        serde_json::from_str(&serde_json::to_string(&test_tree).unwrap()).unwrap();
+    assert!(restored.uses_word("time"));
+    assert!(!restored.uses_word("alias"));
+}
+
+#[test]
+fn test_matching_paths_containing_filler_words() {
+    let mut test_tree = PathTree::new();
+    test_tree.set_by_path("date", "what is the date").unwrap();
+    test_tree.set_by_path("end", "the end").unwrap();
+    test_tree.set_default_normalization(Normalization::default());
+
+    for input in [
+        "what is the date",
+        "what is date",
+        "What is THE date?",
+        "please what is the the date",
+        "what the is date",
+    ] {
+        assert_eq!(
+            Some((String::from("what is the date"), vec![])),
+            test_tree.get_command_and_args_from_path(input),
+            "{}",
+            input
+        );
+    }
+    assert_eq!(
+        Some((String::from("the end"), vec![])),
+        test_tree.get_command_and_args_from_path("End.")
+    );
+
+    test_tree
+        .set_normalization("what is the date", Normalization::without_fillers())
+        .unwrap();
+    assert_eq!(
+        None,
+        test_tree.get_command_and_args_from_path("what is date")
+    );
+    assert_eq!(
+        Some((String::from("what is the date"), vec![])),
+        test_tree.get_command_and_args_from_path("What is the date?")
+    );
+}
+
+#[test]
+fn test_matching_a_long_run_of_filler_words() {
+    let mut test_tree = PathTree::new();
+    test_tree.set_by_path("exit", "exit").unwrap();
+    test_tree
+        .set_by_path("say", "please say <ARG> and <ARG>")
+        .unwrap();
+    test_tree.set_default_normalization(Normalization::default());
+
+    let input = format!("{}exit", "please ".repeat(10));
+    assert_eq!(
+        Some((String::from("exit"), vec![])),
+        test_tree.get_command_and_args_from_path(&input)
+    );
+
+    let input = format!("{}exit", "please ".repeat(MAX_INPUT_TOKENS - 1));
+    assert_eq!(
+        Err(PathTreeErr::NoMatch {
+            path: input.trim().to_owned()
+        }),
+        test_tree.resolve(&input)
+    );
+}
+
+#[test]
+fn test_matching_input_that_is_too_long() {
+    let mut test_tree = PathTree::new();
+    test_tree.set_by_path("say", "say <ARG> and <ARG>").unwrap();
+    test_tree.set_by_path("repeat", "repeat <ARG>").unwrap();
+
+    let input = format!("say {}", "a ".repeat(MAX_INPUT_TOKENS - 1));
+    assert_eq!(
+        Err(PathTreeErr::NoMatch {
+            path: input.trim().to_owned()
+        }),
+        test_tree.resolve(&input)
+    );
+    let input = format!("say {}", "a ".repeat(20_000));
+    assert_eq!(
+        Err(PathTreeErr::InputTooLong {
+            length: 20_001,
+            limit: MAX_INPUT_TOKENS
+        }),
+        test_tree.resolve(&input)
+    );
+    assert_eq!(
+        Some((
+            String::from("repeat <ARG>"),
+            vec!["a ".repeat(20_000).trim().to_owned()]
+        )),
+        test_tree.get_command_and_args_from_path(&format!("repeat \"{}\"", "a ".repeat(20_000)))
+    );
+}
@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_SYNONYMS: [(&str, &str); 4] = [
+    ("clock", "time"),
+    ("delete", "remove"),
+    ("drop", "remove"),
+    ("quit", "exit"),
+];
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+#[serde(from = "BTreeMap<String, String>", into = "BTreeMap<String, String>")]
+pub struct Synonyms {
+    words: BTreeMap<String, String>,
+}
+
+impl From<BTreeMap<String, String>> for Synonyms {
+    fn from(words: BTreeMap<String, String>) -> Self {
+        Self {
+            words: words
+                .into_iter()
+                .map(|(synonym, word)| (synonym.to_lowercase(), word))
+                .collect(),
+        }
+    }
+}
+
+impl From<Synonyms> for BTreeMap<String, String> {
+    fn from(synonyms: Synonyms) -> Self {
+        synonyms.words
+    }
+}
+
+impl Synonyms {
+    pub fn new() -> Self {
+        Self {
+            words: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_defaults() -> Self {
+        Self {
+            words: DEFAULT_SYNONYMS
+                .iter()
+                .map(|(synonym, word)| (synonym.to_string(), word.to_string()))
+                .collect(),
+        }
+    }
+
+    pub fn add(&mut self, synonym: &str, for_word: &str) -> Option<String> {
+        self.words
+            .insert(synonym.to_lowercase(), for_word.to_owned())
+    }
+
+    pub fn remove(&mut self, synonym: &str) -> Option<String> {
+        self.words.remove(&synonym.to_lowercase())
+    }
+
+    pub fn get(&self, synonym: &str) -> Option<&str> {
+        self.words.get(&synonym.to_lowercase()).map(String::as_str)
+    }
+
+    pub fn contains(&self, synonym: &str) -> bool {
+        self.words.contains_key(&synonym.to_lowercase())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.words
+            .iter()
+            .map(|(synonym, word)| (synonym.as_str(), word.as_str()))
+    }
+
+    pub fn synonyms_for<'a>(&'a self, word: &'a str) -> impl Iterator<Item = &'a str> {
+        self.iter()
+            .filter(move |(_, for_word)| *for_word == word)
+            .map(|(synonym, _)| synonym)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+#[test]
+fn test_synonyms_are_case_insensitive() {
+    let mut synonyms = Synonyms::new();
+
+    assert_eq!(None, synonyms.add("Wipe", "remove"));
+    assert_eq!(Some("remove"), synonyms.get("wipe"));
+    assert!(synonyms.contains("WIPE"));
+    assert_eq!(Some(String::from("remove")), synonyms.add("wipe", "clear"));
+    assert_eq!(Some(String::from("clear")), synonyms.remove("WiPe"));
+    assert!(synonyms.is_empty());
+}
+
+#[test]
+fn test_default_synonyms() {
+    let synonyms = Synonyms::with_defaults();
+
+    assert_eq!(Some("time"), synonyms.get("clock"));
+    assert_eq!(
+        vec!["delete", "drop"],
+        synonyms.synonyms_for("remove").collect::<Vec<&str>>()
+    );
+    assert_eq!(
+        r#"{"clock":"time","delete":"remove","drop":"remove","quit":"exit"}"#,
+        serde_json::to_string(&synonyms).unwrap()
+    );
+}
+
+#[test]
+fn test_loaded_synonyms_are_case_insensitive() {
+    let synonyms: Synonyms = serde_json::from_str(r#"{"Wipe":"remove"}"#).unwrap();
+
+    assert_eq!(Some("remove"), synonyms.get("wipe"));
+    assert_eq!(Some("remove"), synonyms.get("WIPE"));
+    assert_eq!(
+        r#"{"wipe":"remove"}"#,
+        serde_json::to_string(&synonyms).unwrap()
+    );
+}
@@ -0,0 +1,85 @@
+use crate::data::locale::Locale;
+use crate::data::pathtree::PathTree;
+use crate::io::appearance::Appearance;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct UserConfig {
+    #[serde(default)]
+    pub aliases: PathTree<String>,
+    #[serde(default)]
+    pub shortcuts: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<Locale>,
+    #[serde(default, skip_serializing_if = "Appearance::is_default")]
+    pub appearance: Appearance,
+}
+
+impl UserConfig {
+    pub fn from_json(contents: &str) -> Result<Self, serde_json::Error> {
+        let config: serde_json::Value = serde_json::from_str(contents)?;
+        if config.get("tree").is_some() {
+            return Ok(UserConfig {
+                aliases: serde_json::from_value(config)?,
+                ..UserConfig::default()
+            });
+        }
+
+        serde_json::from_value(config)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+            && self.shortcuts.is_empty()
+            && self.language.is_none()
+            && self.appearance.is_default()
+    }
+}
+
+#[test]
+fn test_loading_legacy_alias_config() {
+    let legacy = r#"{"tree": {"bye": {"share_count": 1, "value": "exit"}}}"#;
+    let config = UserConfig::from_json(legacy).unwrap();
+
+    assert_eq!(
+        Some(&String::from("exit")),
+        config.aliases.get_value_by_path("bye")
+    );
+    assert!(config.shortcuts.is_empty());
+}
+
+#[test]
+fn test_config_roundtrip() {
+    let mut config = UserConfig::default();
+    assert!(config.is_empty());
+
+    config
+        .shortcuts
+        .insert(String::from("[t]"), String::from("what time is it"));
+    config
+        .aliases
+        .set_by_path(String::from("exit"), "bye")
+        .unwrap();
+    assert!(!config.is_empty());
+
+    let saved = serde_json::to_string(&config).unwrap();
+    assert_eq!(
+        r#"{"aliases":{"tree":{"bye":{"share_count":1,"value":"exit"}}},"shortcuts":{"[t]":"what time is it"}}"#,
+        saved
+    );
+
+    let loaded = UserConfig::from_json(&saved).unwrap();
+    assert_eq!(config.shortcuts, loaded.shortcuts);
+    assert!(loaded.aliases.is_node_active("bye"));
+    assert!(UserConfig::from_json(
+        r#"{"aliases": {"tree": {"a b": {"share_count": 1, "value": "exit"}}}}"#
+    )
+    .is_err());
+    assert!(UserConfig::from_json("{}").unwrap().is_empty());
+
+    let with_language = UserConfig::from_json(r#"{"language": "uk"}"#).unwrap();
+    assert_eq!(Some(Locale::Uk), with_language.language);
+    assert!(!with_language.is_empty());
+}
@@ -0,0 +1,169 @@
+use std::io::IsTerminal;
+
+use serde::{Deserialize, Serialize};
+
+const ALL_THEMES: &[Theme] = &[Theme::Plain, Theme::Violet, Theme::Ocean, Theme::Forest];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    Plain,
+    Violet,
+    Ocean,
+    Forest,
+}
+
+impl Theme {
+    pub fn all() -> &'static [Theme] {
+        ALL_THEMES
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Plain => "plain",
+            Theme::Violet => "violet",
+            Theme::Ocean => "ocean",
+            Theme::Forest => "forest",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Theme> {
+        let name = name.trim().to_lowercase();
+        ALL_THEMES
+            .iter()
+            .copied()
+            .find(|theme| theme.name() == name)
+    }
+
+    fn is_plain(&self) -> bool {
+        *self == Theme::Plain
+    }
+
+    pub fn prompt_colour(&self) -> Option<&'static str> {
+        match self {
+            Theme::Plain => None,
+            Theme::Violet => Some("1;35"),
+            Theme::Ocean => Some("1;36"),
+            Theme::Forest => Some("1;32"),
+        }
+    }
+
+    pub fn banner_colour(&self) -> Option<&'static str> {
+        match self {
+            Theme::Plain => None,
+            Theme::Violet => Some("35"),
+            Theme::Ocean => Some("34"),
+            Theme::Forest => Some("32"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Appearance {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub banner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Theme::is_plain")]
+    pub theme: Theme,
+}
+
+impl Appearance {
+    pub fn is_default(&self) -> bool {
+        *self == Appearance::default()
+    }
+}
+
+pub fn render_template(template: &str, variables: &[(&str, String)]) -> String {
+    let mut rendered = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let after_brace = &rest[start + 1..];
+        let value = after_brace.find('}').and_then(|end| {
+            variables
+                .iter()
+                .find(|(name, _)| *name == &after_brace[..end])
+                .map(|(_, value)| (value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                rendered.push_str(value);
+                rest = &after_brace[end + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = after_brace;
+            }
+        }
+    }
+    rendered.push_str(rest);
+
+    rendered
+}
+
+pub fn paint(text: &str, colour: Option<&str>, colours_enabled: bool) -> String {
+    match colour {
+        Some(colour) if colours_enabled => format!("\x1b[{}m{}\x1b[0m", colour, text),
+        _ => text.to_owned(),
+    }
+}
+
+pub fn colours_enabled() -> bool {
+    std::io::stdout().is_terminal()
+        && std::env::var_os("NO_COLOR").is_none()
+        && std::env::var("TERM").map_or(true, |term| term != "dumb")
+}
+
+#[test]
+fn test_rendering_templates() {
+    let variables = [
+        ("time", String::from("16:20")),
+        ("status", String::from("0")),
+    ];
+    assert_eq!(
+        "[16:20] 0 >",
+        render_template("[{time}] {status} >", &variables)
+    );
+    assert_eq!(
+        "{unknown} {time 16:20}",
+        render_template("{unknown} {time {time}}", &variables)
+    );
+    assert_eq!("no vars {", render_template("no vars {", &variables));
+}
+
+#[test]
+fn test_painting_degrades_without_colours() {
+    assert_eq!(
+        "\x1b[1;35m<<VIO>>\x1b[0m",
+        paint("<<VIO>>", Theme::Violet.prompt_colour(), true)
+    );
+    assert_eq!(
+        "<<VIO>>",
+        paint("<<VIO>>", Theme::Violet.prompt_colour(), false)
+    );
+    assert_eq!(
+        "<<VIO>>",
+        paint("<<VIO>>", Theme::Plain.prompt_colour(), true)
+    );
+}
+
+#[test]
+fn test_default_appearance_is_not_saved() {
+    assert_eq!("{}", serde_json::to_string(&Appearance::default()).unwrap());
+
+    let appearance = Appearance {
+        prompt: Some(String::from("{time} >")),
+        theme: Theme::Ocean,
+        ..Appearance::default()
+    };
+    let saved = serde_json::to_string(&appearance).unwrap();
+    assert_eq!(r#"{"prompt":"{time} >","theme":"ocean"}"#, saved);
+    assert_eq!(
+        appearance,
+        serde_json::from_str::<Appearance>(&saved).unwrap()
+    );
+}
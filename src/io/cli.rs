@@ -0,0 +1,115 @@
+use crate::io::output::OutputMode;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CliOptions {
+    pub commands: Vec<String>,
+    pub quiet: bool,
+    pub output_mode: OutputMode,
+    pub no_colour: bool,
+    pub assume_yes: bool,
+}
+
+impl Default for CliOptions {
+    fn default() -> Self {
+        Self {
+            commands: vec![],
+            quiet: false,
+            output_mode: OutputMode::Text,
+            no_colour: false,
+            assume_yes: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CliAction {
+    Run(CliOptions),
+    GenerateDocumentation { directory: String },
+    ShowUsage,
+    ShowVersion,
+}
+
+pub fn parse_args<I>(args: I) -> Result<CliAction, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter().peekable();
+    if args.peek().map(String::as_str) == Some("docs") {
+        args.next();
+        let directory = args.next().unwrap_or_else(|| String::from("."));
+        if let Some(extra) = args.next() {
+            return Err(format!("unexpected argument {} after docs", extra));
+        }
+        return Ok(CliAction::GenerateDocumentation { directory });
+    }
+
+    let mut options = CliOptions::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-c" | "--command" => match args.next() {
+                Some(command) => options.commands.push(command),
+                None => return Err(format!("{} expects a command to run", arg)),
+            },
+            "-q" | "--quiet" => options.quiet = true,
+            "-y" | "--yes" => options.assume_yes = true,
+            "--no-color" | "--no-colour" => options.no_colour = true,
+            "--output" => match args.next().as_deref().and_then(OutputMode::from_name) {
+                Some(mode) => options.output_mode = mode,
+                None => return Err(String::from("--output expects either \"text\" or \"json\"")),
+            },
+            "-V" | "--version" => return Ok(CliAction::ShowVersion),
+            "-h" | "--help" => return Ok(CliAction::ShowUsage),
+            unknown => return Err(format!("unknown option {}", unknown)),
+        }
+    }
+
+    Ok(CliAction::Run(options))
+}
+
+#[cfg(test)]
+fn parse(args: &[&str]) -> Result<CliAction, String> {
+    parse_args(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn test_parsing_commands_and_flags() {
+    assert_eq!(Ok(CliAction::Run(CliOptions::default())), parse(&[]));
+    assert_eq!(
+        Ok(CliAction::Run(CliOptions {
+            commands: vec![
+                String::from("what time is it"),
+                String::from("please say \"a b\" and c")
+            ],
+            quiet: true,
+            output_mode: OutputMode::Json,
+            no_colour: false,
+            assume_yes: true,
+        })),
+        parse(&[
+            "-c",
+            "what time is it",
+            "--quiet",
+            "--command",
+            "please say \"a b\" and c",
+            "--output",
+            "json",
+            "-y"
+        ])
+    );
+    assert_eq!(Ok(CliAction::ShowVersion), parse(&["-q", "--version"]));
+    assert_eq!(Ok(CliAction::ShowUsage), parse(&["-h"]));
+    assert_eq!(
+        Ok(CliAction::GenerateDocumentation {
+            directory: String::from("docs")
+        }),
+        parse(&["docs", "docs"])
+    );
+}
+
+#[test]
+fn test_rejecting_bad_arguments() {
+    assert!(parse(&["-c"]).is_err());
+    assert!(parse(&["--output", "xml"]).is_err());
+    assert!(parse(&["--frobnicate"]).is_err());
+    assert!(parse(&["docs", "a", "b"]).is_err());
+}
@@ -0,0 +1,251 @@
+use crate::config::{self, CommandHelp};
+use crate::control::commands::CommandCategory;
+use crate::util::string::clone_uppercased;
+
+use std::path::{Path, PathBuf};
+
+const MARKDOWN_FILE_NAME: &str = "violet.md";
+const MAN_PAGE_FILE_NAME: &str = "violet.1";
+
+pub struct CommandDoc {
+    pub path: String,
+    pub category: CommandCategory,
+    pub shortcuts: Vec<String>,
+    pub help: CommandHelp,
+}
+
+pub fn write_documentation(
+    directory: &Path,
+    commands: &[CommandDoc],
+) -> std::io::Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(directory)?;
+    let markdown_path = directory.join(MARKDOWN_FILE_NAME);
+    std::fs::write(&markdown_path, render_markdown(commands))?;
+    let man_page_path = directory.join(MAN_PAGE_FILE_NAME);
+    std::fs::write(&man_page_path, render_man_page(commands))?;
+
+    Ok(vec![markdown_path, man_page_path])
+}
+
+pub fn render_markdown(commands: &[CommandDoc]) -> String {
+    let mut markdown = format!(
+        "# {} command reference\n\n{} {} is a command interpreter. This reference lists every built-in command, grouped by category.\n",
+        config::get_violet_name(),
+        config::get_violet_name(),
+        config::get_violet_version()
+    );
+
+    let mut current_category: Option<CommandCategory> = None;
+    for command in commands {
+        if current_category != Some(command.category) {
+            markdown.push_str(&format!(
+                "\n## {}\n",
+                clone_uppercased(command.category.name())
+            ));
+            current_category = Some(command.category);
+        }
+
+        let help = &command.help;
+        markdown.push_str(&format!(
+            "\n### `{}`\n\n{}\n",
+            command.path,
+            escape_markdown(help.summary)
+        ));
+        if !command.shortcuts.is_empty() {
+            let shortcuts: Vec<String> = command
+                .shortcuts
+                .iter()
+                .map(|shortcut| format!("`{}`", shortcut))
+                .collect();
+            markdown.push_str(&format!("\nShortcut: {}\n", shortcuts.join(", ")));
+        }
+        if !help.arguments.is_empty() {
+            markdown.push_str("\n**Arguments**\n\n");
+            for (index, argument) in help.arguments.iter().enumerate() {
+                markdown.push_str(&format!(
+                    "{}. `<ARG>`: {}\n",
+                    index + 1,
+                    escape_markdown(argument)
+                ));
+            }
+        }
+        if !help.examples.is_empty() {
+            markdown.push_str("\n**Examples**\n");
+            for example in help.examples {
+                markdown.push_str(&format!("\n```text\n{}\n```\n", example));
+            }
+        }
+        if !help.notes.is_empty() {
+            markdown.push_str("\n**Notes**\n\n");
+            for note in help.notes {
+                markdown.push_str(&format!("- {}\n", escape_markdown(note)));
+            }
+        }
+        if !help.related.is_empty() {
+            let related: Vec<String> = help
+                .related
+                .iter()
+                .map(|related| format!("`{}`", related))
+                .collect();
+            markdown.push_str(&format!("\nSee also: {}\n", related.join(", ")));
+        }
+    }
+
+    markdown.push_str("\n## Topics\n");
+    for topic in config::get_help_topics() {
+        markdown.push_str(&format!(
+            "\n### {}\n\n{}\n\n```text\n{}\n```\n",
+            clone_uppercased(topic.name),
+            escape_markdown(&clone_uppercased(topic.summary)),
+            topic.text
+        ));
+    }
+
+    markdown
+}
+
+pub fn render_man_page(commands: &[CommandDoc]) -> String {
+    let name = config::get_violet_name();
+    let mut man_page = format!(
+        ".TH {} 1 \"\" \"{} {}\" \"{} Manual\"\n.SH NAME\n{} \\- a command interpreter\n.SH SYNOPSIS\n.B {}\n.br\n.B {} docs\n[\\fIDIRECTORY\\fR]\n.SH DESCRIPTION\n{} reads commands from the \\fB{}\\fR prompt and runs them.\nThe \\fBdocs\\fR subcommand writes this manual and a Markdown reference into \\fIDIRECTORY\\fR, or the current directory.\n.SH COMMANDS\n",
+        name.to_uppercase(),
+        name.to_lowercase(),
+        config::get_violet_version(),
+        name,
+        name.to_lowercase(),
+        name.to_lowercase(),
+        name.to_lowercase(),
+        name,
+        escape_roff(config::get_violet_prompt().trim())
+    );
+
+    let mut current_category: Option<CommandCategory> = None;
+    for command in commands {
+        if current_category != Some(command.category) {
+            man_page.push_str(&format!(
+                ".SS {}\n",
+                clone_uppercased(command.category.name())
+            ));
+            current_category = Some(command.category);
+        }
+
+        let help = &command.help;
+        man_page.push_str(&format!(
+            ".TP\n.B {}\n{}\n",
+            escape_roff(&command.path),
+            escape_roff(help.summary)
+        ));
+        if !command.shortcuts.is_empty() {
+            man_page.push_str(&format!(
+                ".br\nShortcut: \\fB{}\\fR\n",
+                escape_roff(&command.shortcuts.join(", "))
+            ));
+        }
+        for (index, argument) in help.arguments.iter().enumerate() {
+            man_page.push_str(&format!(
+                ".br\n\\fI<ARG> {}\\fR: {}\n",
+                index + 1,
+                escape_roff(argument)
+            ));
+        }
+        for example in help.examples {
+            man_page.push_str(&format!(".IP\n.nf\n{}\n.fi\n", escape_roff(example)));
+        }
+        for note in help.notes {
+            man_page.push_str(&format!(".IP\nNOTE: {}\n", escape_roff(note)));
+        }
+        if !help.related.is_empty() {
+            man_page.push_str(&format!(
+                ".IP\nSee also: {}\n",
+                escape_roff(&help.related.join(", "))
+            ));
+        }
+    }
+
+    man_page.push_str(".SH TOPICS\n");
+    for topic in config::get_help_topics() {
+        man_page.push_str(&format!(
+            ".SS {}\n.nf\n{}\n.fi\n",
+            clone_uppercased(topic.name),
+            escape_roff(topic.text)
+        ));
+    }
+
+    man_page
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn escape_roff(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let escaped = line.replace('\\', "\\e").replace('-', "\\-");
+            if escaped.starts_with('.') || escaped.starts_with('\'') {
+                format!("\\&{}", escaped)
+            } else {
+                escaped
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+fn create_test_docs() -> Vec<CommandDoc> {
+    vec![
+        CommandDoc {
+            path: String::from("please say <ARG> and <ARG>"),
+            category: CommandCategory::Info,
+            shortcuts: vec![String::from("[psaaa] <ARG> <ARG>")],
+            help: config::Help::please_say_arg_and_arg(),
+        },
+        CommandDoc {
+            path: String::from("exit"),
+            category: CommandCategory::Session,
+            shortcuts: vec![String::from("[e]")],
+            help: config::Help::exit(),
+        },
+    ]
+}
+
+#[test]
+fn test_rendering_markdown() {
+    let markdown = render_markdown(&create_test_docs());
+    assert!(markdown.starts_with("# Violet command reference\n"));
+    assert!(markdown.contains(
+        "\n## Info\n\n### `please say <ARG> and <ARG>`\n\nThis command just echoes the two arguments you provide back at you.\n\nShortcut: `[psaaa] <ARG> <ARG>`\n\n**Arguments**\n\n1. `<ARG>`: the first thing to say\n2. `<ARG>`: the second thing to say\n"
+    ));
+    assert!(markdown
+        .contains("```text\n<<VIO>> please say one and two\nGotcha! Saying one and two!\n```"));
+    assert!(markdown.contains("\n## Session\n\n### `exit`\n"));
+    assert!(markdown.contains("\n## Topics\n\n### Basics\n"));
+}
+
+#[test]
+fn test_rendering_man_page() {
+    let man_page = render_man_page(&create_test_docs());
+    assert!(man_page.starts_with(".TH VIOLET 1 \"\" \"violet "));
+    assert!(man_page.contains(".SS Info\n.TP\n.B please say <ARG> and <ARG>\n"));
+    assert!(man_page.contains("Shortcut: \\fB[psaaa] <ARG> <ARG>\\fR\n"));
+    assert!(man_page.contains(".SS Session\n.TP\n.B exit\n"));
+    for line in man_page.lines() {
+        assert!(!line.starts_with("'"), "unescaped line: {}", line);
+    }
+}
+
+#[test]
+fn test_escaping_markdown() {
+    assert_eq!(
+        "Explains the command specified in the &lt;ARG&gt;.",
+        escape_markdown("Explains the command specified in the <ARG>.")
+    );
+}
+
+#[test]
+fn test_escaping_roff() {
+    assert_eq!("\\&.hidden", escape_roff(".hidden"));
+    assert_eq!("a \\- b \\e n", escape_roff("a - b \\ n"));
+    assert_eq!("one\n\\&'two", escape_roff("one\n'two"));
+}
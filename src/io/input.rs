@@ -1,15 +1,109 @@
+use crate::util::treepath::{TreePath, TreePathErr};
+
 use std::io;
-use std::io::Write;
+use std::io::{BufRead, IsTerminal, Write};
 
-pub fn get_user_input(prompt: String) -> String {
-    print!("{}", prompt);
-    std::io::stdout()
-        .flush()
-        .expect("ERROR: flushing stdout failed!");
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal()
+}
+
+pub fn get_user_input(reader: &mut impl BufRead, prompt: &str) -> io::Result<Option<String>> {
+    if !prompt.is_empty() {
+        print!("{}", prompt);
+        io::stdout().flush()?;
+    }
 
     let mut user_input = String::new();
-    match io::stdin().read_line(&mut user_input) {
-        Ok(_) => String::from(user_input.trim()),
-        Err(error) => format!("ERROR: read_line(): {}", error),
+    match reader.read_line(&mut user_input)? {
+        0 => Ok(None),
+        _ => Ok(Some(String::from(user_input.trim()))),
+    }
+}
+
+pub fn get_command(
+    reader: &mut impl BufRead,
+    prompt: &str,
+    continuation_prompt: &str,
+) -> io::Result<Option<String>> {
+    let mut command = match get_user_input(reader, prompt)? {
+        Some(line) => line,
+        None => return Ok(None),
+    };
+
+    loop {
+        let continued = match command.strip_suffix('\\') {
+            Some(line) => line.trim_end().to_owned(),
+            None if TreePath::tokenize(&command) == Err(TreePathErr::UnclosedQuote) => {
+                command.clone()
+            }
+            None => return Ok(Some(command)),
+        };
+
+        match get_user_input(reader, continuation_prompt)? {
+            Some(line) => command = format!("{} {}", continued, line).trim().to_owned(),
+            None => return Ok(Some(continued)),
+        }
     }
 }
+
+#[test]
+fn test_reading_lines_until_eof() {
+    let mut reader = io::Cursor::new("  what time is it \r\n\nexit");
+    assert_eq!(
+        Some(String::from("what time is it")),
+        get_user_input(&mut reader, "").unwrap()
+    );
+    assert_eq!(
+        Some(String::new()),
+        get_user_input(&mut reader, "").unwrap()
+    );
+    assert_eq!(
+        Some(String::from("exit")),
+        get_user_input(&mut reader, "").unwrap()
+    );
+    assert_eq!(None, get_user_input(&mut reader, "").unwrap());
+}
+
+#[test]
+fn test_reporting_read_errors() {
+    let mut reader = io::Cursor::new(vec![0x66, 0xff, 0x0a]);
+    assert_eq!(
+        io::ErrorKind::InvalidData,
+        get_user_input(&mut reader, "").unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_continuing_commands_over_several_lines() {
+    let mut reader = io::Cursor::new(
+        "please say \\\n  hi and \\\nbye\nexplain command \"what\n is your\nname\"\nexit",
+    );
+    assert_eq!(
+        Some(String::from("please say hi and bye")),
+        get_command(&mut reader, "", "").unwrap()
+    );
+    assert_eq!(
+        Some(String::from("explain command \"what is your name\"")),
+        get_command(&mut reader, "", "").unwrap()
+    );
+    assert_eq!(
+        Some(String::from("exit")),
+        get_command(&mut reader, "", "").unwrap()
+    );
+    assert_eq!(None, get_command(&mut reader, "", "").unwrap());
+}
+
+#[test]
+fn test_ending_input_inside_a_continued_command() {
+    let mut reader = io::Cursor::new("please say a and \\\n");
+    assert_eq!(
+        Some(String::from("please say a and")),
+        get_command(&mut reader, "", "").unwrap()
+    );
+
+    let mut reader = io::Cursor::new("please say \"a and b");
+    assert_eq!(
+        Some(String::from("please say \"a and b")),
+        get_command(&mut reader, "", "").unwrap()
+    );
+}
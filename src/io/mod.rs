@@ -1 +1,6 @@
+pub mod appearance;
+pub mod cli;
+pub mod docs;
 pub mod input;
+pub mod output;
+pub mod transcript;
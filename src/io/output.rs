@@ -0,0 +1,404 @@
+use crate::io::appearance::paint;
+use crate::util::calc::Number;
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Output,
+    Info,
+    Notice,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn label(&self) -> Option<&'static str> {
+        match self {
+            Severity::Output => None,
+            Severity::Info => Some("INFO:"),
+            Severity::Notice => Some("NOTICE:"),
+            Severity::Warning => Some("WARNING:"),
+            Severity::Error => Some("ERROR:"),
+        }
+    }
+
+    fn colour(&self) -> Option<&'static str> {
+        match self {
+            Severity::Output => None,
+            Severity::Info => Some("36"),
+            Severity::Notice => Some("1;35"),
+            Severity::Warning => Some("33"),
+            Severity::Error => Some("1;31"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputMode {
+    Text,
+    Json,
+}
+
+impl OutputMode {
+    pub fn from_name(name: &str) -> Option<OutputMode> {
+        match name.trim().to_lowercase().as_str() {
+            "text" => Some(OutputMode::Text),
+            "json" => Some(OutputMode::Json),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputMessage {
+    pub severity: Severity,
+    pub text: String,
+}
+
+impl OutputMessage {
+    pub fn render(&self) -> String {
+        format_message(OutputMode::Text, false, self.severity, self.text.clone())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommandRecord {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job: Option<usize>,
+    pub input: String,
+    pub command: Option<String>,
+    pub args: Vec<String>,
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Number>,
+    pub messages: Vec<OutputMessage>,
+}
+
+fn format_message(
+    mode: OutputMode,
+    colours_enabled: bool,
+    severity: Severity,
+    text: String,
+) -> String {
+    match (mode, severity.label()) {
+        (OutputMode::Json, _) => {
+            serde_json::to_string(&OutputMessage { severity, text }).unwrap_or_default()
+        }
+        (OutputMode::Text, Some(label)) => format!(
+            "{} {}",
+            paint(label, severity.colour(), colours_enabled),
+            text
+        ),
+        (OutputMode::Text, None) => text,
+    }
+}
+
+#[derive(Clone)]
+pub struct Notifier {
+    mode: OutputMode,
+    colours_enabled: bool,
+    prompt: Arc<Mutex<String>>,
+    captured: Option<Arc<Mutex<Vec<String>>>>,
+}
+
+impl Notifier {
+    pub fn notify(&self, severity: Severity, text: String) {
+        let line = format_message(self.mode, self.colours_enabled, severity, text);
+        if let Some(captured) = &self.captured {
+            captured.lock().unwrap().push(line);
+            return;
+        }
+
+        let prompt = self.prompt.lock().unwrap();
+        let mut stdout = std::io::stdout().lock();
+        let written = if prompt.is_empty() {
+            writeln!(stdout, "{}", line)
+        } else {
+            write!(stdout, "\n{}\n{}", line, prompt)
+        };
+        let _ = written.and_then(|_| stdout.flush());
+    }
+}
+
+pub struct Output {
+    mode: OutputMode,
+    colours_enabled: bool,
+    quiet: bool,
+    muted: bool,
+    current: Option<CommandRecord>,
+    prompt: Arc<Mutex<String>>,
+    captured: Option<Arc<Mutex<Vec<String>>>>,
+}
+
+impl Output {
+    pub fn new(mode: OutputMode, colours_enabled: bool) -> Self {
+        Self {
+            mode,
+            colours_enabled: colours_enabled && mode == OutputMode::Text,
+            quiet: false,
+            muted: false,
+            current: None,
+            prompt: Arc::new(Mutex::new(String::new())),
+            captured: None,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn capturing(mode: OutputMode) -> Self {
+        Self {
+            captured: Some(Arc::new(Mutex::new(vec![]))),
+            ..Output::new(mode, false)
+        }
+    }
+
+    pub fn mode(&self) -> OutputMode {
+        self.mode
+    }
+
+    pub fn colours_enabled(&self) -> bool {
+        self.colours_enabled
+    }
+
+    pub fn is_quiet(&self) -> bool {
+        self.quiet
+    }
+
+    pub fn set_quiet(&mut self, quiet: bool) {
+        self.quiet = quiet;
+    }
+
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+    }
+
+    pub fn set_prompt(&self, prompt: &str) {
+        *self.prompt.lock().unwrap() = prompt.to_owned();
+    }
+
+    pub fn interrupt_prompt(&mut self) -> String {
+        let prompt = std::mem::take(&mut *self.prompt.lock().unwrap());
+        if !prompt.is_empty() && self.captured.is_none() {
+            println!();
+        }
+
+        prompt
+    }
+
+    pub fn restore_prompt(&mut self, prompt: String) {
+        if !prompt.is_empty() && self.captured.is_none() {
+            print!("{}", prompt);
+            let _ = std::io::stdout().flush();
+        }
+        *self.prompt.lock().unwrap() = prompt;
+    }
+
+    pub fn notifier(&self) -> Notifier {
+        Notifier {
+            mode: self.mode,
+            colours_enabled: self.colours_enabled,
+            prompt: Arc::clone(&self.prompt),
+            captured: self.captured.clone(),
+        }
+    }
+
+    pub fn emit(&mut self, severity: Severity, text: String) {
+        if self.quiet && severity == Severity::Info {
+            return;
+        }
+        if let Some(record) = &mut self.current {
+            record.messages.push(OutputMessage {
+                severity,
+                text: text.clone(),
+            });
+            if self.mode == OutputMode::Json {
+                return;
+            }
+        }
+        if !self.muted {
+            let line = format_message(self.mode, self.colours_enabled, severity, text);
+            self.write_line(line);
+        }
+    }
+
+    pub fn print(&mut self, text: String) {
+        self.emit(Severity::Output, text);
+    }
+
+    pub fn info(&mut self, text: String) {
+        self.emit(Severity::Info, text);
+    }
+
+    pub fn notice(&mut self, text: String) {
+        self.emit(Severity::Notice, text);
+    }
+
+    pub fn warning(&mut self, text: String) {
+        self.emit(Severity::Warning, text);
+    }
+
+    pub fn error(&mut self, text: String) {
+        self.emit(Severity::Error, text);
+    }
+
+    pub fn highlight(&self, text: &str) -> String {
+        paint(text, Some("1"), self.colours_enabled)
+    }
+
+    pub fn highlight_path(&self, path: &str) -> String {
+        path.split(' ')
+            .map(|word| match word {
+                "<ARG>" => paint(word, Some("33"), self.colours_enabled),
+                word => self.highlight(word),
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    pub fn begin_command(&mut self, input: &str) {
+        self.current = Some(CommandRecord {
+            input: input.to_owned(),
+            ..CommandRecord::default()
+        });
+    }
+
+    pub fn set_job(&mut self, id: usize) {
+        if let Some(record) = &mut self.current {
+            record.job = Some(id);
+        }
+    }
+
+    pub fn set_command(&mut self, command: &str, args: &[String]) {
+        if let Some(record) = &mut self.current {
+            record.command = Some(command.to_owned());
+            record.args = args.to_vec();
+        }
+    }
+
+    pub fn set_result(&mut self, result: Number) {
+        if let Some(record) = &mut self.current {
+            record.result = Some(result);
+        }
+    }
+
+    pub fn end_command(&mut self, success: bool) -> Option<CommandRecord> {
+        let mut record = self.current.take()?;
+        record.success = success;
+        if self.mode == OutputMode::Json && !self.muted {
+            let line = serde_json::to_string(&record).unwrap_or_default();
+            self.write_line(line);
+        }
+
+        Some(record)
+    }
+
+    pub fn suspend_command(&mut self) -> Option<CommandRecord> {
+        self.current.take()
+    }
+
+    pub fn resume_command(&mut self, record: Option<CommandRecord>) {
+        self.current = record;
+    }
+
+    fn write_line(&mut self, line: String) {
+        match &self.captured {
+            Some(captured) => captured.lock().unwrap().push(line),
+            None => println!("{}", line),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn take_captured(&mut self) -> Vec<String> {
+        match &self.captured {
+            Some(captured) => std::mem::take(&mut *captured.lock().unwrap()),
+            None => vec![],
+        }
+    }
+}
+
+#[test]
+fn test_text_output_labels_severities() {
+    let mut output = Output::capturing(OutputMode::Text);
+    output.print(String::from("Hello!"));
+    output.info(String::from("loaded"));
+    output.warning(String::from("careful"));
+    output.error(String::from("broken"));
+    assert_eq!(
+        vec![
+            "Hello!",
+            "INFO: loaded",
+            "WARNING: careful",
+            "ERROR: broken"
+        ],
+        output.take_captured()
+    );
+}
+
+#[test]
+fn test_json_output_emits_a_line_per_command() {
+    let mut output = Output::capturing(OutputMode::Json);
+    output.info(String::from("starting"));
+    output.begin_command("please say a and b");
+    output.set_command(
+        "please say <ARG> and <ARG>",
+        &[String::from("a"), String::from("b")],
+    );
+    output.print(String::from("Gotcha. Saying a and b!"));
+    output.end_command(true);
+    output.begin_command("nope");
+    output.error(String::from("nope: command does not exist."));
+    output.end_command(false);
+
+    assert_eq!(
+        vec![
+            r#"{"severity":"info","text":"starting"}"#,
+            r#"{"input":"please say a and b","command":"please say <ARG> and <ARG>","args":["a","b"],"success":true,"messages":[{"severity":"output","text":"Gotcha. Saying a and b!"}]}"#,
+            r#"{"input":"nope","command":null,"args":[],"success":false,"messages":[{"severity":"error","text":"nope: command does not exist."}]}"#,
+        ],
+        output.take_captured()
+    );
+}
+
+#[test]
+fn test_quiet_output_drops_info() {
+    let mut output = Output::capturing(OutputMode::Text);
+    output.set_quiet(true);
+    output.info(String::from("loaded"));
+    output.warning(String::from("careful"));
+    output.print(String::from("Hello!"));
+    assert_eq!(vec!["WARNING: careful", "Hello!"], output.take_captured());
+}
+
+#[test]
+fn test_json_output_is_never_coloured() {
+    let output = Output::new(OutputMode::Json, true);
+    assert!(!output.colours_enabled());
+    assert_eq!("exit", output.highlight("exit"));
+    assert_eq!("say <ARG>", output.highlight_path("say <ARG>"));
+}
+
+#[test]
+fn test_highlighting_paths_and_args() {
+    let output = Output::new(OutputMode::Text, true);
+    assert_eq!(
+        "\x1b[1msay\x1b[0m \x1b[33m<ARG>\x1b[0m",
+        output.highlight_path("say <ARG>")
+    );
+}
+
+#[test]
+fn test_notifying_from_another_thread() {
+    let mut output = Output::capturing(OutputMode::Json);
+    output.set_quiet(true);
+    let notifier = output.notifier();
+    std::thread::spawn(move || notifier.notify(Severity::Notice, String::from("tea is ready")))
+        .join()
+        .unwrap();
+    assert_eq!(
+        vec![r#"{"severity":"notice","text":"tea is ready"}"#],
+        output.take_captured()
+    );
+}
@@ -0,0 +1,173 @@
+use crate::io::output::CommandRecord;
+
+use chrono::{Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptEntry {
+    pub time: String,
+    #[serde(flatten)]
+    pub record: CommandRecord,
+}
+
+pub struct Recorder {
+    path: PathBuf,
+    file: File,
+    entries: usize,
+}
+
+impl Recorder {
+    pub fn start(path: &Path) -> std::io::Result<Recorder> {
+        Ok(Recorder {
+            path: path.to_path_buf(),
+            file: File::create(path)?,
+            entries: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn entries(&self) -> usize {
+        self.entries
+    }
+
+    pub fn write(&mut self, record: CommandRecord) -> std::io::Result<()> {
+        let entry = TranscriptEntry {
+            time: Local::now().to_rfc3339_opts(SecondsFormat::Millis, false),
+            record,
+        };
+        let line = serde_json::to_string(&entry)?;
+        writeln!(self.file, "{}", line)?;
+        self.entries += 1;
+
+        Ok(())
+    }
+}
+
+pub fn parse_transcript(contents: &str) -> Result<Vec<TranscriptEntry>, String> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|the_err| {
+                format!("line {} isn't a transcript entry: {}", index + 1, the_err)
+            })
+        })
+        .collect()
+}
+
+fn render_record(record: &CommandRecord) -> Vec<String> {
+    let mut lines: Vec<String> = record
+        .messages
+        .iter()
+        .flat_map(|message| {
+            message
+                .render()
+                .lines()
+                .map(str::to_owned)
+                .collect::<Vec<String>>()
+        })
+        .collect();
+    if !record.success {
+        lines.push(String::from("(the command failed)"));
+    }
+
+    lines
+}
+
+pub fn diff_records(recorded: &CommandRecord, replayed: &CommandRecord) -> Vec<String> {
+    diff_lines(&render_record(recorded), &render_record(replayed))
+}
+
+pub fn diff_lines(before: &[String], after: &[String]) -> Vec<String> {
+    if before == after {
+        return vec![];
+    }
+
+    let mut common = vec![vec![0usize; after.len() + 1]; before.len() + 1];
+    for i in (0..before.len()).rev() {
+        for j in (0..after.len()).rev() {
+            common[i][j] = if before[i] == after[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < before.len() || j < after.len() {
+        if i < before.len() && j < after.len() && before[i] == after[j] {
+            diff.push(format!("  {}", before[i]));
+            i += 1;
+            j += 1;
+        } else if j == after.len() || (i < before.len() && common[i + 1][j] >= common[i][j + 1]) {
+            diff.push(format!("- {}", before[i]));
+            i += 1;
+        } else {
+            diff.push(format!("+ {}", after[j]));
+            j += 1;
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+fn record_saying(lines: &[&str], success: bool) -> CommandRecord {
+    use crate::io::output::{OutputMessage, Severity};
+
+    CommandRecord {
+        input: String::from("say"),
+        success,
+        messages: lines
+            .iter()
+            .map(|line| OutputMessage {
+                severity: Severity::Output,
+                text: line.to_string(),
+            })
+            .collect(),
+        ..CommandRecord::default()
+    }
+}
+
+#[test]
+fn test_parsing_a_transcript() {
+    let contents = concat!(
+        r#"{"time":"2030-01-01T10:00:00.000+00:00","input":"exit","command":"exit","args":[],"success":true,"messages":[{"severity":"output","text":"Bye!"}]}"#,
+        "\n\n",
+        r#"{"time":"2030-01-01T10:00:01.000+00:00","job":2,"input":"calculate 2","command":"calculate <ARG>","args":["2"],"success":true,"result":2,"messages":[]}"#,
+    );
+    let entries = parse_transcript(contents).unwrap();
+    assert_eq!(2, entries.len());
+    assert_eq!(Some(String::from("exit")), entries[0].record.command);
+    assert_eq!(Some(2), entries[1].record.job);
+    assert_eq!(
+        Err(String::from(
+            "line 1 isn't a transcript entry: expected ident at line 1 column 2"
+        )),
+        parse_transcript("nope")
+    );
+}
+
+#[test]
+fn test_diffing_records() {
+    let recorded = record_saying(&["a", "b", "c"], true);
+    assert!(diff_records(&recorded, &recorded).is_empty());
+    assert_eq!(
+        vec!["  a", "- b", "+ B", "  c"],
+        diff_records(&recorded, &record_saying(&["a", "B", "c"], true))
+    );
+    assert_eq!(
+        vec!["  a", "  b", "  c", "+ (the command failed)"],
+        diff_records(&recorded, &record_saying(&["a", "b", "c"], false))
+    );
+}
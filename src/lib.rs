@@ -0,0 +1,5 @@
+pub mod config;
+pub mod control;
+pub mod data;
+pub mod io;
+pub mod util;
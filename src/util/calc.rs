@@ -0,0 +1,516 @@
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Number {
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Number::Integer(value) => value as f64,
+            Number::Float(value) => value,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.as_f64() == 0.0
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Integer(value) => write!(f, "{}", value),
+            Number::Float(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(Number),
+    Word(String),
+    Operator(char),
+    OpenParen,
+    CloseParen,
+}
+
+const MAX_NESTING: usize = 256;
+
+const UNITS: &[&str] = &[
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: &[(&str, i64)] = &[
+    ("twenty", 20),
+    ("thirty", 30),
+    ("forty", 40),
+    ("fifty", 50),
+    ("sixty", 60),
+    ("seventy", 70),
+    ("eighty", 80),
+    ("ninety", 90),
+];
+
+const SCALES: &[(&str, i64)] = &[
+    ("thousand", 1_000),
+    ("million", 1_000_000),
+    ("billion", 1_000_000_000),
+    ("trillion", 1_000_000_000_000),
+];
+
+const OPERATOR_WORDS: &[(&[&str], char)] = &[
+    (&["plus"], '+'),
+    (&["minus"], '-'),
+    (&["negative"], '-'),
+    (&["times"], '*'),
+    (&["multiplied", "by"], '*'),
+    (&["divided", "by"], '/'),
+    (&["over"], '/'),
+    (&["modulo"], '%'),
+    (&["mod"], '%'),
+    (&["to", "the", "power", "of"], '^'),
+];
+
+fn too_big() -> String {
+    String::from("the result is too big to calculate")
+}
+
+fn number_word_value(word: &str) -> Option<i64> {
+    UNITS
+        .iter()
+        .position(|unit| *unit == word)
+        .map(|value| value as i64)
+        .or_else(|| {
+            TENS.iter()
+                .find(|(tens, _)| *tens == word)
+                .map(|(_, value)| *value)
+        })
+}
+
+fn is_number_word(word: &str) -> bool {
+    word == "hundred"
+        || number_word_value(word).is_some()
+        || SCALES.iter().any(|(scale, _)| *scale == word)
+}
+
+pub fn parse_number_words(text: &str) -> Result<i64, String> {
+    let words: Vec<String> = text
+        .to_lowercase()
+        .split(|ch: char| ch.is_whitespace() || ch == '-')
+        .filter(|word| !word.is_empty() && *word != "and")
+        .map(String::from)
+        .collect();
+    if words.is_empty() {
+        return Err(String::from("there's no number"));
+    }
+
+    let mut total: i64 = 0;
+    let mut current: Option<i64> = None;
+    let mut last_scale: Option<i64> = None;
+    for (index, word) in words.iter().enumerate() {
+        let is_article = (word == "a" || word == "an")
+            && words
+                .get(index + 1)
+                .is_some_and(|next| next == "hundred" || SCALES.iter().any(|(s, _)| s == next));
+        if is_article {
+            current = Some(current.unwrap_or(0) + 1);
+        } else if let Some(value) = number_word_value(word) {
+            current = Some(current.unwrap_or(0) + value);
+        } else if word == "hundred" {
+            current = Some(current.unwrap_or(1).checked_mul(100).ok_or_else(too_big)?);
+        } else if let Some((_, scale)) = SCALES.iter().find(|(scale, _)| scale == word) {
+            // "a thousand million" scales everything said so far, "a million and a thousand"
+            // only the words since the last scale.
+            let (kept, amount) = match last_scale {
+                Some(last) if last <= *scale => (0, total + current.unwrap_or(0)),
+                _ => (total, current.unwrap_or(1)),
+            };
+            total = amount
+                .checked_mul(*scale)
+                .and_then(|amount| amount.checked_add(kept))
+                .ok_or_else(too_big)?;
+            current = None;
+            last_scale = Some(*scale);
+        } else {
+            return Err(format!("\"{}\" isn't a number Violet knows", word));
+        }
+    }
+
+    total.checked_add(current.unwrap_or(0)).ok_or_else(too_big)
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = expression.to_lowercase().chars().collect();
+    let mut tokens: Vec<Token> = vec![];
+    let mut index = 0;
+    while index < chars.len() {
+        let ch = chars[index];
+        if ch.is_whitespace() {
+            index += 1;
+        } else if ch.is_ascii_digit() || ch == '.' {
+            let start = index;
+            while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.') {
+                index += 1;
+            }
+            let literal: String = chars[start..index].iter().collect();
+            let number = if literal.contains('.') {
+                literal
+                    .parse::<f64>()
+                    .map(Number::Float)
+                    .map_err(|_| format!("\"{}\" isn't a number", literal))?
+            } else {
+                literal
+                    .parse::<i64>()
+                    .map(Number::Integer)
+                    .map_err(|_| format!("{} is too big to calculate with", literal))?
+            };
+            tokens.push(Token::Number(number));
+        } else if ch.is_alphabetic() {
+            let start = index;
+            while index < chars.len()
+                && (chars[index].is_alphabetic()
+                    || (chars[index] == '-'
+                        && chars
+                            .get(index + 1)
+                            .is_some_and(|next| next.is_alphabetic())))
+            {
+                index += 1;
+            }
+            tokens.push(Token::Word(chars[start..index].iter().collect()));
+        } else {
+            index += 1;
+            tokens.push(match ch {
+                '(' => Token::OpenParen,
+                ')' => Token::CloseParen,
+                '*' if chars.get(index) == Some(&'*') => {
+                    index += 1;
+                    Token::Operator('^')
+                }
+                '+' | '-' | '*' | '/' | '%' | '^' => Token::Operator(ch),
+                '×' => Token::Operator('*'),
+                '÷' => Token::Operator('/'),
+                _ => return Err(format!("'{}' isn't something Violet can calculate", ch)),
+            });
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn resolve_words(tokens: Vec<Token>, variables: &[(&str, Number)]) -> Result<Vec<Token>, String> {
+    let mut resolved: Vec<Token> = vec![];
+    let mut index = 0;
+    while index < tokens.len() {
+        let word = match &tokens[index] {
+            Token::Word(word) => word.as_str(),
+            token => {
+                resolved.push(token.clone());
+                index += 1;
+                continue;
+            }
+        };
+
+        let words_ahead = |count: usize| -> Vec<&str> {
+            tokens[index..]
+                .iter()
+                .take(count)
+                .map(|token| match token {
+                    Token::Word(word) => word.as_str(),
+                    _ => "",
+                })
+                .collect()
+        };
+        if let Some((phrase, operator)) = OPERATOR_WORDS
+            .iter()
+            .find(|(phrase, _)| words_ahead(phrase.len()) == *phrase)
+        {
+            resolved.push(Token::Operator(*operator));
+            index += phrase.len();
+        } else if word == "squared" || word == "cubed" {
+            resolved.push(Token::Operator('^'));
+            resolved.push(Token::Number(Number::Integer(if word == "squared" {
+                2
+            } else {
+                3
+            })));
+            index += 1;
+        } else if let Some((_, value)) = variables.iter().find(|(name, _)| *name == word) {
+            resolved.push(Token::Number(*value));
+            index += 1;
+        } else {
+            let mut words: Vec<&str> = vec![];
+            while let Some(Token::Word(word)) = tokens.get(index) {
+                let continues_number = is_number_word(word.split('-').next().unwrap_or(word))
+                    || ((word == "and" || word == "a" || word == "an") && !words.is_empty())
+                    || ((word == "a" || word == "an")
+                        && matches!(tokens.get(index + 1), Some(Token::Word(next)) if is_number_word(next)));
+                if !continues_number {
+                    break;
+                }
+                words.push(word);
+                index += 1;
+            }
+            if words.is_empty() {
+                return Err(format!(
+                    "\"{}\" isn't a number or an operation Violet knows",
+                    word
+                ));
+            }
+            resolved.push(Token::Number(Number::Integer(parse_number_words(
+                &words.join(" "),
+            )?)));
+        }
+    }
+
+    Ok(resolved)
+}
+
+fn checked(value: f64) -> Result<Number, String> {
+    if value.is_finite() {
+        Ok(Number::Float(value))
+    } else {
+        Err(too_big())
+    }
+}
+
+fn apply(operator: char, left: Number, right: Number) -> Result<Number, String> {
+    if (operator == '/' || operator == '%') && right.is_zero() {
+        return Err(String::from("you can't divide by zero"));
+    }
+
+    match (left, right) {
+        (Number::Integer(left), Number::Integer(right)) => {
+            let result = match operator {
+                '+' => left.checked_add(right),
+                '-' => left.checked_sub(right),
+                '*' => left.checked_mul(right),
+                '/' if left.checked_rem(right).is_some_and(|rest| rest != 0) => {
+                    return checked(left as f64 / right as f64);
+                }
+                '/' => left.checked_div(right),
+                '%' => Some(left.wrapping_rem(right)),
+                '^' if right < 0 => return checked((left as f64).powf(right as f64)),
+                _ => u32::try_from(right)
+                    .ok()
+                    .and_then(|right| left.checked_pow(right)),
+            };
+            result.map(Number::Integer).ok_or_else(too_big)
+        }
+        (left, right) => {
+            let (left, right) = (left.as_f64(), right.as_f64());
+            checked(match operator {
+                '+' => left + right,
+                '-' => left - right,
+                '*' => left * right,
+                '/' => left / right,
+                '%' => left % right,
+                _ => left.powf(right),
+            })
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next_operator(&mut self, operators: &[char]) -> Option<char> {
+        match self.peek() {
+            Some(Token::Operator(operator)) if operators.contains(operator) => {
+                let operator = *operator;
+                self.position += 1;
+                Some(operator)
+            }
+            _ => None,
+        }
+    }
+
+    fn expression(&mut self) -> Result<Number, String> {
+        let mut value = self.term()?;
+        while let Some(operator) = self.next_operator(&['+', '-']) {
+            value = apply(operator, value, self.term()?)?;
+        }
+
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<Number, String> {
+        let mut value = self.unary()?;
+        while let Some(operator) = self.next_operator(&['*', '/', '%']) {
+            value = apply(operator, value, self.unary()?)?;
+        }
+
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<Number, String> {
+        if self.depth >= MAX_NESTING {
+            return Err(String::from("the expression is nested too deeply"));
+        }
+
+        self.depth += 1;
+        let value = match self.next_operator(&['+', '-']) {
+            Some('-') => self
+                .unary()
+                .and_then(|value| apply('-', Number::Integer(0), value)),
+            Some(_) => self.unary(),
+            None => self.power(),
+        };
+        self.depth -= 1;
+
+        value
+    }
+
+    fn power(&mut self) -> Result<Number, String> {
+        let base = self.primary()?;
+        match self.next_operator(&['^']) {
+            Some(_) => apply('^', base, self.unary()?),
+            None => Ok(base),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Number, String> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        match token {
+            Some(Token::Number(number)) => Ok(number),
+            Some(Token::OpenParen) => {
+                let value = self.expression()?;
+                match self.peek() {
+                    Some(Token::CloseParen) => {
+                        self.position += 1;
+                        Ok(value)
+                    }
+                    _ => Err(String::from("a parenthesis is never closed")),
+                }
+            }
+            Some(Token::Operator(operator)) => {
+                Err(format!("'{}' is missing a number before it", operator))
+            }
+            Some(Token::CloseParen) => {
+                Err(String::from("a parenthesis is closed but never opened"))
+            }
+            Some(Token::Word(word)) => Err(format!("\"{}\" isn't a number", word)),
+            None => Err(String::from("the expression ends too early")),
+        }
+    }
+}
+
+pub fn evaluate(expression: &str, variables: &[(&str, Number)]) -> Result<Number, String> {
+    let tokens = resolve_words(tokenize(expression)?, variables)?;
+    if tokens.is_empty() {
+        return Err(String::from("there's nothing to calculate"));
+    }
+
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        depth: 0,
+    };
+    let value = parser.expression()?;
+    match parser.peek() {
+        None => Ok(value),
+        Some(Token::CloseParen) => Err(String::from("a parenthesis is closed but never opened")),
+        Some(_) => Err(String::from(
+            "two numbers follow each other without an operation between them",
+        )),
+    }
+}
+
+#[test]
+fn test_parsing_number_words() {
+    assert_eq!(Ok(7), parse_number_words("seven"));
+    assert_eq!(Ok(21), parse_number_words("twenty-one"));
+    assert_eq!(Ok(105), parse_number_words("one hundred and five"));
+    assert_eq!(Ok(2_300), parse_number_words("two thousand three hundred"));
+    assert_eq!(Ok(100), parse_number_words("a hundred"));
+    assert!(parse_number_words("seven dwarves").is_err());
+    assert!(parse_number_words("ten million trillion").is_err());
+}
+
+#[test]
+fn test_evaluating_expressions() {
+    let calculate = |expression: &str| evaluate(expression, &[]);
+    assert_eq!(Ok(Number::Integer(14)), calculate("2 + 3 * 4"));
+    assert_eq!(Ok(Number::Integer(20)), calculate("(2 + 3) * 4"));
+    assert_eq!(Ok(Number::Integer(-4)), calculate("-2 ^ 2"));
+    assert_eq!(Ok(Number::Integer(512)), calculate("2 ** 3 ^ 2"));
+    assert_eq!(Ok(Number::Float(2.5)), calculate("5 / 2"));
+    assert_eq!(Ok(Number::Integer(3)), calculate("6 / 2"));
+    assert_eq!(Ok(Number::Float(0.75)), calculate("1.5 * 0.5"));
+    assert_eq!(Ok(Number::Integer(1)), calculate("10 % 3"));
+    assert_eq!(Ok(Number::Integer(21)), calculate("three times seven"));
+    assert_eq!(
+        Ok(Number::Integer(43)),
+        calculate("twenty one multiplied by two plus one")
+    );
+    assert_eq!(Ok(Number::Integer(81)), calculate("nine squared"));
+    assert_eq!(
+        Ok(Number::Integer(12)),
+        evaluate("ans times 2", &[("ans", Number::Integer(6))])
+    );
+}
+
+#[test]
+fn test_reporting_calculation_errors() {
+    let calculate = |expression: &str| evaluate(expression, &[]);
+    assert_eq!(
+        Err(String::from("you can't divide by zero")),
+        calculate("1 / (2 - 2)")
+    );
+    assert_eq!(Err(too_big()), calculate("9223372036854775807 + 1"));
+    assert_eq!(Err(too_big()), calculate("2 ^ 64"));
+    assert_eq!(Err(too_big()), calculate("10.0 ^ 400"));
+    assert_eq!(Ok(Number::Integer(0)), calculate("((-2) ^ 63) % -1"));
+    assert_eq!(
+        Ok(Number::Integer(0)),
+        calculate("(0 - 9223372036854775807 - 1) mod -1")
+    );
+    assert_eq!(Err(too_big()), calculate("((-2) ^ 63) / -1"));
+    assert!(calculate("99999999999999999999").is_err());
+    assert!(calculate("(1 + 2").is_err());
+    assert!(calculate("1 + 2)").is_err());
+    assert!(calculate("1 2").is_err());
+    assert!(calculate("1 +").is_err());
+    assert!(calculate("seven dwarves").is_err());
+    assert!(calculate("").is_err());
+
+    let nested = |count: usize| format!("{}1{}", "(".repeat(count), ")".repeat(count));
+    assert_eq!(Ok(Number::Integer(1)), calculate(&nested(100)));
+    let too_deep = Err(String::from("the expression is nested too deeply"));
+    assert_eq!(too_deep, calculate(&nested(20000)));
+    assert_eq!(too_deep, calculate(&format!("{}1", "-".repeat(20000))));
+    assert_eq!(too_deep, calculate(&format!("2{}", " ^ 1".repeat(20000))));
+}
@@ -1,2 +1,4 @@
+pub mod calc;
 pub mod string;
+pub mod time;
 pub mod treepath;
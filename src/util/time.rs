@@ -0,0 +1,331 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use chrono_tz::{Tz, TZ_VARIANTS};
+
+const DURATION_UNITS: &[(&[&str], i64)] = &[
+    (
+        &[
+            "s",
+            "sec",
+            "secs",
+            "second",
+            "seconds",
+            "секунда",
+            "секунду",
+            "секунди",
+            "секунд",
+        ],
+        1,
+    ),
+    (
+        &[
+            "m",
+            "min",
+            "mins",
+            "minute",
+            "minutes",
+            "хвилина",
+            "хвилину",
+            "хвилини",
+            "хвилин",
+        ],
+        60,
+    ),
+    (
+        &[
+            "h",
+            "hr",
+            "hrs",
+            "hour",
+            "hours",
+            "година",
+            "годину",
+            "години",
+            "годин",
+        ],
+        60 * 60,
+    ),
+    (&["d", "day", "days", "день", "дні", "днів"], 24 * 60 * 60),
+];
+
+fn unit_seconds(unit: &str) -> Option<i64> {
+    DURATION_UNITS
+        .iter()
+        .find(|(names, _)| names.contains(&unit))
+        .map(|(_, seconds)| *seconds)
+}
+
+fn split_number_and_unit(word: &str) -> Vec<String> {
+    let mut parts: Vec<String> = vec![];
+    for ch in word.chars() {
+        let is_digit = ch.is_ascii_digit() || ch == '.';
+        match parts.last_mut() {
+            Some(last)
+                if last
+                    .chars()
+                    .last()
+                    .is_some_and(|prev| (prev.is_ascii_digit() || prev == '.') == is_digit) =>
+            {
+                last.push(ch)
+            }
+            _ => parts.push(ch.to_string()),
+        }
+    }
+
+    parts
+}
+
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let words: Vec<String> = text
+        .to_lowercase()
+        .split(|ch: char| ch.is_whitespace() || ch == ',')
+        .filter(|word| !word.is_empty() && *word != "and" && *word != "і" && *word != "та")
+        .flat_map(split_number_and_unit)
+        .collect();
+    if words.is_empty() {
+        return Err(String::from("the duration is empty"));
+    }
+
+    let mut total_millis: i64 = 0;
+    let mut words = words.iter();
+    while let Some(amount) = words.next() {
+        let amount: f64 = match amount.as_str() {
+            "a" | "an" | "one" | "одна" | "один" | "одну" => 1.0,
+            number => number.parse().map_err(|_| {
+                format!(
+                    "\"{}\" isn't a number of seconds, minutes, hours or days",
+                    number
+                )
+            })?,
+        };
+        let unit = words
+            .next()
+            .ok_or_else(|| String::from("the duration is missing its unit, like \"minutes\""))?;
+        let seconds = unit_seconds(unit)
+            .ok_or_else(|| format!("\"{}\" isn't a unit of time Violet knows", unit))?;
+        let millis = amount * seconds as f64 * 1000.0;
+        if !millis.is_finite()
+            || millis < 0.0
+            || millis > Duration::days(365 * 100).num_milliseconds() as f64
+        {
+            return Err(String::from("the duration is out of range"));
+        }
+        total_millis += millis.round() as i64;
+    }
+
+    Ok(Duration::milliseconds(total_millis))
+}
+
+fn plural(amount: i64, unit: &str) -> String {
+    format!("{} {}{}", amount, unit, if amount == 1 { "" } else { "s" })
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let duration = if duration < Duration::zero() {
+        -duration
+    } else {
+        duration
+    };
+    if duration < Duration::minutes(1) {
+        let seconds = duration.num_milliseconds() as f64 / 1000.0;
+        return format!(
+            "{:.1} second{}",
+            seconds,
+            if (seconds - 1.0).abs() < 0.05 {
+                ""
+            } else {
+                "s"
+            }
+        );
+    }
+
+    let seconds = duration.num_seconds();
+    let parts: Vec<String> = [
+        (seconds / 86400, "day"),
+        (seconds % 86400 / 3600, "hour"),
+        (seconds % 3600 / 60, "minute"),
+        (seconds % 60, "second"),
+    ]
+    .iter()
+    .filter(|(amount, _)| *amount > 0)
+    .map(|(amount, unit)| plural(*amount, unit))
+    .collect();
+
+    match parts.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        _ => parts.join(""),
+    }
+}
+
+fn parse_clock_time(text: &str) -> Option<NaiveTime> {
+    let text = text.replace(' ', "");
+    match text.as_str() {
+        "noon" | "midday" | "полудня" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" | "опівночі" => return NaiveTime::from_hms_opt(0, 0, 0),
+        _ => (),
+    }
+
+    let (clock, afternoon) = if let Some(clock) = text.strip_suffix("am") {
+        (clock, Some(false))
+    } else if let Some(clock) = text.strip_suffix("pm") {
+        (clock, Some(true))
+    } else {
+        (text.as_str(), None)
+    };
+    let mut numbers = clock.splitn(2, ':');
+    let hour: u32 = numbers.next()?.parse().ok()?;
+    let minute: u32 = match numbers.next() {
+        Some(minute) => minute.parse().ok()?,
+        None if afternoon.is_some() => 0,
+        None => return None,
+    };
+    let hour = match afternoon {
+        Some(_) if hour == 0 || hour > 12 => return None,
+        Some(true) => hour % 12 + 12,
+        Some(false) => hour % 12,
+        None => hour,
+    };
+
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+fn next_yearly(now: DateTime<Local>, month: u32, day: u32) -> Option<NaiveDateTime> {
+    let this_year = NaiveDate::from_ymd_opt(now.year(), month, day)?.and_hms_opt(0, 0, 0)?;
+    if this_year > now.naive_local() {
+        Some(this_year)
+    } else {
+        NaiveDate::from_ymd_opt(now.year() + 1, month, day)?.and_hms_opt(0, 0, 0)
+    }
+}
+
+pub fn parse_moment(text: &str, now: DateTime<Local>) -> Result<DateTime<Local>, String> {
+    let normalized = text.trim().to_lowercase();
+    let normalized = normalized
+        .strip_prefix("the ")
+        .unwrap_or(&normalized)
+        .trim_end_matches(['?', '!', '.'])
+        .to_owned();
+
+    let moment = match normalized.as_str() {
+        "new year" | "new year's" | "new years" | "новий рік" | "нового року" => {
+            next_yearly(now, 1, 1)
+        }
+        "christmas" | "різдво" | "різдва" => next_yearly(now, 12, 25),
+        "tomorrow" | "завтра" => (now.date_naive() + Duration::days(1)).and_hms_opt(0, 0, 0),
+        _ => None,
+    };
+    let moment = match moment {
+        Some(moment) => moment,
+        None => {
+            if let Ok(moment) = NaiveDateTime::parse_from_str(&normalized, "%Y-%m-%d %H:%M") {
+                moment
+            } else if let Ok(date) = NaiveDate::parse_from_str(&normalized, "%Y-%m-%d") {
+                date.and_hms_opt(0, 0, 0).unwrap_or_default()
+            } else if let Some(time) = parse_clock_time(&normalized) {
+                let today = now.date_naive().and_time(time);
+                if today > now.naive_local() {
+                    today
+                } else {
+                    today + Duration::days(1)
+                }
+            } else {
+                return Err(String::from(
+                    "use a time like 17:30 or 5pm, a date like 2030-12-31 (optionally followed by a time), \"tomorrow\", \"christmas\" or \"new year\"",
+                ));
+            }
+        }
+    };
+
+    Local
+        .from_local_datetime(&moment)
+        .earliest()
+        .ok_or_else(|| String::from("that time doesn't exist in your time zone"))
+}
+
+pub fn find_time_zone(name: &str) -> Option<Tz> {
+    let wanted = name.trim().replace(' ', "_").to_lowercase();
+    match wanted.as_str() {
+        "" => return None,
+        "utc" | "gmt" | "zulu" => return Some(Tz::UTC),
+        _ => (),
+    }
+
+    TZ_VARIANTS
+        .iter()
+        .find(|zone| zone.name().to_lowercase() == wanted)
+        .or_else(|| {
+            TZ_VARIANTS.iter().find(|zone| {
+                zone.name()
+                    .rsplit('/')
+                    .next()
+                    .is_some_and(|city| city.to_lowercase() == wanted)
+            })
+        })
+        .copied()
+}
+
+#[cfg(test)]
+fn test_now() -> DateTime<Local> {
+    Local
+        .from_local_datetime(
+            &NaiveDate::from_ymd_opt(2030, 6, 15)
+                .unwrap()
+                .and_hms_opt(14, 0, 0)
+                .unwrap(),
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_parsing_durations() {
+    assert_eq!(Ok(Duration::minutes(5)), parse_duration("5 minutes"));
+    assert_eq!(Ok(Duration::seconds(90)), parse_duration("1m30s"));
+    assert_eq!(
+        Ok(Duration::minutes(90)),
+        parse_duration("an hour and 30 minutes")
+    );
+    assert_eq!(Ok(Duration::minutes(90)), parse_duration("1.5 hours"));
+    assert_eq!(Ok(Duration::days(2)), parse_duration("2 дні"));
+    assert!(parse_duration("").is_err());
+    assert!(parse_duration("5").is_err());
+    assert!(parse_duration("five parsecs").is_err());
+    assert!(parse_duration("-5 minutes").is_err());
+    assert!(parse_duration("99999999999999999999 days").is_err());
+}
+
+#[test]
+fn test_formatting_durations() {
+    assert_eq!("3.4 seconds", format_duration(Duration::milliseconds(3400)));
+    assert_eq!("1.0 second", format_duration(Duration::seconds(1)));
+    assert_eq!("1 minute", format_duration(Duration::seconds(60)));
+    assert_eq!(
+        "1 day, 2 hours and 1 second",
+        format_duration(Duration::seconds(86400 + 7200 + 1))
+    );
+}
+
+#[test]
+fn test_parsing_moments() {
+    let now = test_now();
+    let at = |text: &str| parse_moment(text, now).map(|moment| moment.naive_local().to_string());
+    assert_eq!(Ok(String::from("2030-06-15 17:30:00")), at("17:30"));
+    assert_eq!(Ok(String::from("2030-06-15 17:00:00")), at("5 pm"));
+    assert_eq!(Ok(String::from("2030-06-16 09:15:00")), at("9:15am"));
+    assert_eq!(Ok(String::from("2030-06-16 12:00:00")), at("noon"));
+    assert_eq!(Ok(String::from("2030-12-25 00:00:00")), at("Christmas"));
+    assert_eq!(Ok(String::from("2031-01-01 00:00:00")), at("the new year"));
+    assert_eq!(
+        Ok(String::from("2029-01-01 08:00:00")),
+        at("2029-01-01 08:00")
+    );
+    assert!(at("13pm").is_err());
+    assert!(at("someday").is_err());
+}
+
+#[test]
+fn test_finding_time_zones() {
+    assert_eq!(Some(Tz::Europe__Kyiv), find_time_zone("Kyiv"));
+    assert_eq!(Some(Tz::America__New_York), find_time_zone("new york"));
+    assert_eq!(Some(Tz::Asia__Tokyo), find_time_zone("asia/tokyo"));
+    assert_eq!(Some(Tz::UTC), find_time_zone("UTC"));
+    assert_eq!(None, find_time_zone("Atlantis"));
+}
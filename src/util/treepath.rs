@@ -1,14 +1,95 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TreePathErr {
+    EmptyPath,
+    NotEnoughArguments { expected: usize, provided: usize },
+    UnclosedQuote,
+    InvalidShortcut { shortcut: String },
+}
+
+impl fmt::Display for TreePathErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TreePathErr::EmptyPath => write!(f, "path is empty"),
+            TreePathErr::NotEnoughArguments { expected, provided } => write!(
+                f,
+                "path expects at least {} argument(s), but only {} were provided",
+                expected, provided
+            ),
+            TreePathErr::UnclosedQuote => write!(f, "a quoted argument is never closed"),
+            TreePathErr::InvalidShortcut { shortcut } => write!(
+                f,
+                "[{}] is not a valid shortcut, shortcuts are single words enclosed in [ and ], like [t]",
+                shortcut
+            ),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PathToken {
+    pub text: String,
+    pub quoted: bool,
+}
+
 pub struct TreePath;
 
 impl TreePath {
     pub fn create_path(pathify_this: &str) -> Vec<String> {
         pathify_this
-            .trim()
             .split_whitespace()
             .map(|elem| elem.to_string())
             .collect::<Vec<String>>()
     }
 
+    pub fn tokenize(input: &str) -> Result<Vec<PathToken>, TreePathErr> {
+        let mut tokens: Vec<PathToken> = vec![];
+        let mut quoted: Option<Vec<&str>> = None;
+
+        for word in input.split_whitespace() {
+            match quoted.as_mut() {
+                None if word.starts_with('"') => {
+                    let rest = &word[1..];
+                    if !rest.is_empty() && rest.ends_with('"') {
+                        tokens.push(PathToken {
+                            text: rest[..rest.len() - 1].to_owned(),
+                            quoted: true,
+                        });
+                    } else if rest.is_empty() {
+                        quoted = Some(vec![]);
+                    } else {
+                        quoted = Some(vec![rest]);
+                    }
+                }
+                None => tokens.push(PathToken {
+                    text: word.to_owned(),
+                    quoted: false,
+                }),
+                Some(words) if word.ends_with('"') => {
+                    words.push(&word[..word.len() - 1]);
+                    tokens.push(PathToken {
+                        text: words
+                            .iter()
+                            .filter(|word| !word.is_empty())
+                            .copied()
+                            .collect::<Vec<&str>>()
+                            .join(" "),
+                        quoted: true,
+                    });
+                    quoted = None;
+                }
+                Some(words) => words.push(word),
+            }
+        }
+
+        if quoted.is_some() {
+            return Err(TreePathErr::UnclosedQuote);
+        }
+
+        Ok(tokens)
+    }
+
     pub fn get_path_hierarchy(path: &str) -> Vec<String> {
         let path = TreePath::create_path(path);
         let mut current_path = String::new();
@@ -27,34 +108,43 @@ impl TreePath {
         TreePath::create_path(path).join(" ")
     }
 
+    pub fn extend_path(path: &str, node_to_append: &str) -> String {
+        if path.is_empty() {
+            node_to_append.to_owned()
+        } else {
+            format!("{} {}", path, node_to_append)
+        }
+    }
+
     pub fn append_path_node(path: &[String], node_to_append: &str) -> String {
         let mut newpathvec = path.to_owned();
         newpathvec.push(node_to_append.to_owned());
         newpathvec.join(" ")
     }
 
-    pub fn get_last_node(of_path: &str) -> Option<String> {
-        let path = TreePath::create_path(of_path);
-        match path.last() {
-            Some(node) => Some(node.to_owned()),
-            None => None,
-        }
-    }
-
-    pub fn reconstruct_argumented_path(path_to_reconstruct: &str, args: Vec<String>) -> String {
+    pub fn reconstruct_argumented_path(
+        path_to_reconstruct: &str,
+        args: Vec<String>,
+    ) -> Result<String, TreePathErr> {
         let mut pathvec = TreePath::create_path(path_to_reconstruct);
+        let expected = TreePath::count_x_nodes_for_path(path_to_reconstruct, "<ARG>");
+        if args.len() < expected {
+            return Err(TreePathErr::NotEnoughArguments {
+                expected,
+                provided: args.len(),
+            });
+        }
 
-        let mut arg_index: usize = 0;
+        let mut args = args.into_iter();
         for node in pathvec.iter_mut() {
             if node.as_str() == "<ARG>" {
-                let new_arg = format!("\"{}\"", args[arg_index]).to_string();
-                *node = new_arg;
-
-                arg_index += 1;
+                if let Some(arg) = args.next() {
+                    *node = format!("\"{}\"", arg);
+                }
             }
         }
 
-        pathvec.join(" ")
+        Ok(pathvec.join(" "))
     }
 
     pub fn count_x_nodes_for_path(path: &str, x_node: &str) -> usize {
@@ -64,45 +154,68 @@ impl TreePath {
             .count()
     }
 
-    pub fn create_shortcut(path: &str, serial: usize) -> String {
-        if path.is_empty() {
-            panic!("PANIC: TreePath::create_shortcut(): couldn't create the shortcut, source path is empty!");
+    pub fn create_shortcut(path: &str, serial: usize) -> Result<String, TreePathErr> {
+        let pathvec = TreePath::create_path(path);
+        if pathvec.is_empty() {
+            return Err(TreePathErr::EmptyPath);
         }
 
-        let pathvec = TreePath::create_path(path);
         let mut shortcut: String = String::from('[');
         let mut arg_count: usize = 0;
 
-        let serial_str = serial.to_string();
-        let alias_serial = if serial_str.as_str() == "1" {
-            ""
-        } else {
-            serial_str.as_str()
-        };
-
         for node in pathvec {
             if node.as_str() == "<ARG>" {
                 shortcut.push('a');
                 arg_count += 1;
-            } else {
-                shortcut.push(node.chars().next().unwrap());
+            } else if let Some(first_letter) = node.chars().next() {
+                shortcut.push(first_letter);
             }
         }
 
-        if !alias_serial.is_empty() {
-            shortcut.push_str(alias_serial);
+        if serial > 1 {
+            shortcut.push_str(&serial.to_string());
         }
         shortcut.push(']');
         if arg_count != 0 {
             shortcut.push(' ');
             shortcut.push_str(vec!["<ARG>"; arg_count].join(" ").as_str());
         }
-        shortcut
+        Ok(shortcut)
     }
 
-    pub fn is_path_a_shortcut(path: &str) -> bool {
-        let first_node = TreePath::create_path(path)[0].to_owned();
+    pub fn create_shortcut_path(shortcut: &str, for_path: &str) -> Result<String, TreePathErr> {
+        let invalid = || TreePathErr::InvalidShortcut {
+            shortcut: TreePath::prettify(shortcut),
+        };
+        let name = match TreePath::create_path(shortcut).as_slice() {
+            [name] => name.to_owned(),
+            _ => return Err(invalid()),
+        };
+        let inner = name
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+            .ok_or_else(invalid)?;
+        if inner.is_empty() || inner.contains(['[', ']', '"']) {
+            return Err(invalid());
+        }
+
+        let arg_count = TreePath::count_x_nodes_for_path(for_path, "<ARG>");
+        let mut shortcut_path = vec![name];
+        shortcut_path.extend(vec![String::from("<ARG>"); arg_count]);
+        Ok(shortcut_path.join(" "))
+    }
 
-        first_node.starts_with('[') && first_node.ends_with(']')
+    pub fn get_shortcut_name(shortcut_path: &str) -> Option<String> {
+        match shortcut_path.split_whitespace().next() {
+            Some(name) if TreePath::is_path_a_shortcut(name) => Some(name.to_owned()),
+            _ => None,
+        }
+    }
+
+    pub fn is_path_a_shortcut(path: &str) -> bool {
+        match path.split_whitespace().next() {
+            Some(first_node) => first_node.starts_with('[') && first_node.ends_with(']'),
+            None => false,
+        }
     }
 }
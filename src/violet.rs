@@ -1,11 +1,65 @@
-mod config;
-mod control;
-mod data;
-mod io;
-mod util;
+use std::path::Path;
+use std::process::exit;
 
-use crate::control::interpreter::Interpreter;
+use violet::config;
+use violet::control::interpreter::Interpreter;
+use violet::io::appearance;
+use violet::io::cli::{self, CliAction};
+use violet::io::docs;
+use violet::io::output::Output;
 
 fn main() {
-    Interpreter::new().run_repl();
+    let options = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(CliAction::Run(options)) => options,
+        Ok(CliAction::GenerateDocumentation { directory }) => {
+            match docs::write_documentation(
+                Path::new(&directory),
+                &Interpreter::builtin_documentation(),
+            ) {
+                Ok(written) => {
+                    for file in written {
+                        println!("INFO: wrote {}", file.display());
+                    }
+                }
+                Err(the_err) => {
+                    eprintln!(
+                        "ERROR: couldn't write the documentation to {}: {}",
+                        directory, the_err
+                    );
+                    exit(1);
+                }
+            }
+            return;
+        }
+        Ok(CliAction::ShowUsage) => {
+            println!("{}", config::get_usage_message());
+            return;
+        }
+        Ok(CliAction::ShowVersion) => {
+            println!(
+                "{} {}",
+                config::get_violet_name(),
+                config::get_violet_version()
+            );
+            return;
+        }
+        Err(the_err) => {
+            eprintln!("ERROR: {}\n\n{}", the_err, config::get_usage_message());
+            exit(2);
+        }
+    };
+
+    let mut output = Output::new(
+        options.output_mode,
+        appearance::colours_enabled() && !options.no_colour,
+    );
+    output.set_quiet(options.quiet);
+    let mut interpreter = Interpreter::with_output(output);
+    interpreter.set_confirmations(!options.assume_yes);
+    let status = if options.commands.is_empty() {
+        interpreter.run_repl()
+    } else {
+        interpreter.run_commands(&options.commands)
+    };
+    exit(status);
 }